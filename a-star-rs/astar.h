@@ -0,0 +1,270 @@
+/*
+ * C interface of the a-star library.
+ *
+ * Keep this file in sync with the #[no_mangle] functions of src/ffi.rs,
+ * the test_header_declares_every_export test fails otherwise.
+ */
+
+#ifndef ASTAR_H
+#define ASTAR_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* status codes of get_path and get_path_with_costs */
+#define PATH_FOUND 0
+#define PATH_BUFFER_TOO_SMALL 6
+
+/* error codes of astar_find_path and astar_find_path_with_costs */
+#define ASTAR_ERROR_UNREACHABLE -1
+#define ASTAR_ERROR_DEPARTURE_BLOCKED -2
+#define ASTAR_ERROR_ARRIVAL_BLOCKED -3
+#define ASTAR_ERROR_OUT_OF_BOUNDS -4
+#define ASTAR_ERROR_DEPARTURE_IS_ARRIVAL -5
+#define ASTAR_ERROR_BUFFER_TOO_SMALL -6
+#define ASTAR_ERROR_NULL_POINTER -7
+#define ASTAR_ERROR_COST_OVERFLOW -10
+
+/* flow field values of astar_grid_flow_field */
+#define ASTAR_UNREACHABLE_DISTANCE 0xFFFFFFFF
+#define ASTAR_DIRECTION_NONE -1
+#define ASTAR_DIRECTION_RIGHT 0
+#define ASTAR_DIRECTION_BOTTOM_RIGHT 1
+#define ASTAR_DIRECTION_BOTTOM 2
+#define ASTAR_DIRECTION_BOTTOM_LEFT 3
+#define ASTAR_DIRECTION_LEFT 4
+#define ASTAR_DIRECTION_TOP_LEFT 5
+#define ASTAR_DIRECTION_TOP 6
+#define ASTAR_DIRECTION_TOP_RIGHT 7
+
+/* movement models of astar_grid_set_movement */
+#define ASTAR_MOVEMENT_FOUR_DIRECTIONS 0
+#define ASTAR_MOVEMENT_EIGHT_DIRECTIONS 1
+#define ASTAR_MOVEMENT_NO_CORNER_CUTTING 2
+#define ASTAR_MOVEMENT_NO_SQUEEZING 3
+
+/* research algorithms of astar_grid_set_algorithm */
+#define ASTAR_ALGORITHM_ASTAR 0
+#define ASTAR_ALGORITHM_DIJKSTRA 1
+#define ASTAR_ALGORITHM_BREADTH_FIRST 2
+#define ASTAR_ALGORITHM_GREEDY_BEST_FIRST 3
+#define ASTAR_ALGORITHM_JUMP_POINT 4
+#define ASTAR_ALGORITHM_BIDIRECTIONAL 5
+
+/* grid kept between researches, created by astar_grid_new */
+typedef struct AstarGrid AstarGrid;
+
+/* horizontal and vertical positions of a node */
+typedef struct AstarPosition {
+    uint32_t horizontal;
+    uint32_t vertical;
+} AstarPosition;
+
+/* cost, length and research statistics of a path */
+typedef struct AstarPathResult {
+    /* sum of the movement costs along the path, terrain weights included */
+    uint32_t cost;
+    /* euclidean length, 1 between two neighbour nodes */
+    double length;
+    /* nodes whose children have been added to the open list */
+    size_t expanded_amount;
+    /* nodes added to the open list */
+    size_t generated_amount;
+    /* largest amount of nodes into the open list at once */
+    size_t peak_open_amount;
+    /* nodes into the closed list, 0 if it has not been requested */
+    size_t closed_amount;
+} AstarPathResult;
+
+/*
+ * Researches the path from the departure to the arrival and writes
+ * its indices (departure excluded, arrival included) into path.
+ *
+ * weights is either NULL or an array of width * height terrain weights
+ * (0 for an unusuable node). walls and path may be NULL if their length is 0.
+ *
+ * Returns the amount of written indices, or a negative ASTAR_ERROR_* code.
+ * Nothing is written if the path does not fit into path_capacity indices;
+ * a path never contains more than width * height - 1 indices.
+ */
+ptrdiff_t astar_find_path(
+    uint32_t *path,
+    size_t path_capacity,
+    const uint32_t *walls,
+    size_t walls_amount,
+    const uint8_t *weights,
+    uint32_t width,
+    uint32_t height,
+    uint32_t departure,
+    uint32_t arrival
+);
+
+/* same as astar_find_path, with custom movement costs (10 and 14 by default) */
+ptrdiff_t astar_find_path_with_costs(
+    uint32_t *path,
+    size_t path_capacity,
+    const uint32_t *walls,
+    size_t walls_amount,
+    const uint8_t *weights,
+    uint32_t width,
+    uint32_t height,
+    uint32_t departure,
+    uint32_t arrival,
+    uint32_t horizontal_or_vertical_cost,
+    uint32_t diagonal_cost
+);
+
+/* positions of the index on a grid of the given width ({ 0, 0 } if 0) */
+AstarPosition astar_get_position(
+    uint32_t width,
+    uint32_t index
+);
+
+/*
+ * Creates a grid of usuable nodes with a terrain weight of 1,
+ * returns NULL if width * height does not fit into 32 bits.
+ */
+AstarGrid *astar_grid_new(
+    uint32_t width,
+    uint32_t height
+);
+
+/* returns 0 or a negative ASTAR_ERROR_* code */
+ptrdiff_t astar_grid_set_blocked(
+    AstarGrid *grid,
+    uint32_t index,
+    bool blocked
+);
+
+/*
+ * Sets the terrain weight of the node (0 blocks the node).
+ * Returns 0 or a negative ASTAR_ERROR_* code.
+ */
+ptrdiff_t astar_grid_set_cost(
+    AstarGrid *grid,
+    uint32_t index,
+    uint8_t cost
+);
+
+/*
+ * Sets the movement model, one of the ASTAR_MOVEMENT_* values
+ * (ASTAR_MOVEMENT_EIGHT_DIRECTIONS by default).
+ * Returns 0 or a negative ASTAR_ERROR_* code.
+ */
+ptrdiff_t astar_grid_set_movement(
+    AstarGrid *grid,
+    uint32_t movement
+);
+
+/*
+ * Sets the base costs of the movements (10 and 14 by default).
+ * Returns 0 or a negative ASTAR_ERROR_* code.
+ */
+ptrdiff_t astar_grid_set_move_costs(
+    AstarGrid *grid,
+    uint32_t horizontal_or_vertical_cost,
+    uint32_t diagonal_cost
+);
+
+/*
+ * Sets the research algorithm, one of the ASTAR_ALGORITHM_* values
+ * (ASTAR_ALGORITHM_ASTAR by default).
+ * Returns 0 or a negative ASTAR_ERROR_* code.
+ */
+ptrdiff_t astar_grid_set_algorithm(
+    AstarGrid *grid,
+    uint32_t algorithm
+);
+
+/*
+ * Same as astar_find_path on the grid. The memory of the research
+ * is kept into the grid for the next ones.
+ */
+ptrdiff_t astar_grid_find_path(
+    AstarGrid *grid,
+    uint32_t departure,
+    uint32_t arrival,
+    uint32_t *path,
+    size_t path_capacity
+);
+
+/*
+ * Same as astar_grid_find_path, and writes the cost, the length and the
+ * research statistics of the path into result, which must not be NULL.
+ * The closed node indices are written into closed, sorted in ascending
+ * order, unless closed_capacity is 0 (closed may then be NULL and
+ * closed_amount is 0).
+ * Nothing is written if the path or the requested closed list does not fit.
+ */
+ptrdiff_t astar_grid_find_path_result(
+    AstarGrid *grid,
+    uint32_t departure,
+    uint32_t arrival,
+    uint32_t *path,
+    size_t path_capacity,
+    uint32_t *closed,
+    size_t closed_capacity,
+    AstarPathResult *result
+);
+
+/*
+ * Writes the cost to the nearest goal (ASTAR_UNREACHABLE_DISTANCE
+ * if none) and the direction of the next movement (ASTAR_DIRECTION_NONE
+ * for the goals and if no goal can be reached) of every node.
+ * distances and directions must contain at least width * height items.
+ * Returns 0 or a negative ASTAR_ERROR_* code.
+ */
+ptrdiff_t astar_grid_flow_field(
+    AstarGrid *grid,
+    const uint32_t *goals,
+    size_t goals_amount,
+    uint32_t *distances,
+    int8_t *directions,
+    size_t nodes_amount
+);
+
+/* releases the grid, NULL is ignored */
+void astar_grid_free(
+    AstarGrid *grid
+);
+
+/*
+ * Previous interface: returns PATH_FOUND, PATH_BUFFER_TOO_SMALL
+ * or the opposite of the ASTAR_ERROR_* code.
+ */
+int get_path(
+    uint32_t *path,
+    size_t path_capacity,
+    const uint32_t *walls,
+    size_t walls_amount,
+    const uint8_t *weights,
+    uint32_t width,
+    uint32_t height,
+    uint32_t departure,
+    uint32_t arrival
+);
+
+int get_path_with_costs(
+    uint32_t *path,
+    size_t path_capacity,
+    const uint32_t *walls,
+    size_t walls_amount,
+    const uint8_t *weights,
+    uint32_t width,
+    uint32_t height,
+    uint32_t departure,
+    uint32_t arrival,
+    uint32_t horizontal_or_vertical_cost,
+    uint32_t diagonal_cost
+);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
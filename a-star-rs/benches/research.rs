@@ -0,0 +1,304 @@
+//! Benchmarks of the path research on large grids.
+//!
+//! Run with `cargo bench`, every research is timed on a release build.
+//! The A* research is compared with the vector based open and closed
+//! lists used before the binary heap, on 256 x 256 grids only unless
+//! `cargo bench -- --all-vectors` is run (about ten minutes
+//! on 1024 x 1024 grids).
+
+extern crate astar;
+
+use std::env;
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use astar::{
+    Grid,
+    HierarchicalGrid,
+    JumpTable,
+    SearchAlgorithm,
+    SearchContext,
+};
+
+/// Amount of researches performed per grid.
+const RUNS: u32 = 10;
+
+/// Creates a square grid with scattered unusuable nodes and a wall
+/// across the middle, only open on the right side. The departure is
+/// the top left corner and the arrival is the bottom left corner
+/// (see `bench_research`), so the research has to go around the wall.
+///
+/// # Arguments:
+///
+/// * `size` - the number of nodes per line and per column
+///
+/// # Returns:
+///
+/// The created grid.
+fn create_walled_grid(size: usize) -> Grid {
+
+    let mut grid = Grid::new(
+        size,
+        size,
+    );
+
+    for vertical_position in 0..size {
+        for horizontal_position in 1..size {
+
+            if (horizontal_position * 7 + vertical_position * 13) % 10 != 0 {
+                continue;
+            }
+
+            let index = vertical_position * size + horizontal_position;
+            grid.get_node_by_index(index)
+                .set_unusuable();
+        }
+    }
+
+    for horizontal_position in 0..size - 2 {
+        grid.get_node_by_index(size / 2 * size + horizontal_position)
+            .set_unusuable();
+    }
+
+    grid
+}
+
+/// Researches a path like the A* research did before the binary heap:
+/// the whole open list is scanned to find the node with the smallest
+/// score, and both lists are scanned to know if a child is in them.
+/// Kept as the reference of the benchmarks.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid to research the path on
+/// * `departure` - the departure cell index
+/// * `arrival` - the arrival cell index
+///
+/// # Returns:
+///
+/// The cost of the path, or None if the arrival is unreachable.
+fn research_with_vectors(
+    grid: &Grid,
+    departure: usize,
+    arrival: usize,
+) -> Option<u32> {
+
+    let mut costs = vec![0; grid.get_nodes_amount()];
+    let mut open_list = vec![departure];
+    let mut closed_list = Vec::new();
+    let mut children = Vec::new();
+
+    while !open_list.is_empty() {
+
+        let position = (0..open_list.len())
+            .min_by_key(|position| {
+                let index = open_list[*position];
+                costs[index] + grid.estimate_heuristic(index, arrival)
+            })
+            .expect("the open list is not empty");
+
+        let current = open_list.remove(position);
+        closed_list.push(current);
+
+        if current == arrival {
+            return Some(costs[current]);
+        }
+
+        grid.generate_children_list(current, &mut children);
+
+        for child in children.iter() {
+
+            if
+                closed_list.contains(child) ||
+                !grid.get_node(*child).is_usuable()
+            {
+                continue;
+            }
+
+            let cost = costs[current] + grid.get_movement_cost(current, *child);
+
+            if !open_list.contains(child) {
+                open_list.push(*child);
+            } else if costs[*child] <= cost {
+                continue;
+            }
+
+            costs[*child] = cost;
+        }
+    }
+
+    None
+}
+
+/// Researches the path of a walled grid once with the vector based
+/// open and closed lists, and prints the duration of the research
+/// and how much faster the current A* research is.
+///
+/// # Arguments:
+///
+/// * `size` - the number of nodes per line and per column
+/// * `heap_duration` - the average duration of the current A* research
+fn bench_vector_research(
+    size: usize,
+    heap_duration: Duration,
+) {
+
+    let grid = create_walled_grid(size);
+
+    let start = Instant::now();
+
+    research_with_vectors(&grid, 0, (size - 1) * size)
+        .expect("the arrival is reachable");
+
+    let duration = start.elapsed();
+
+    println!(
+        "research {} x {} (vectors, before the binary heap): {:?} \
+per research, the binary heap is {:.0} times faster",
+        size,
+        size,
+        duration,
+        duration.as_secs_f64() / heap_duration.as_secs_f64(),
+    );
+}
+
+/// Researches the path of a walled grid several times, with the same
+/// research memory, and prints the average duration of one research.
+///
+/// # Arguments:
+///
+/// * `size` - the number of nodes per line and per column
+/// * `algorithm` - the research algorithm
+/// * `jump_table` - true to precompute the jumps of the jump point research
+///
+/// # Returns:
+///
+/// The average duration of one research.
+fn bench_research(
+    size: usize,
+    algorithm: SearchAlgorithm,
+    jump_table: bool,
+) -> Duration {
+
+    let grid = create_walled_grid(size);
+    let mut search = SearchContext::new();
+    let mut total = Duration::from_secs(0);
+
+    search.set_algorithm(algorithm);
+
+    let table = if jump_table {
+        Some(JumpTable::new(&grid).expect("the grid is supported"))
+    } else {
+        None
+    };
+
+    for _ in 0..RUNS {
+
+        let start = Instant::now();
+
+        match table {
+            Some(ref table) => search.research_path_with_jump_table(
+                &grid,
+                table,
+                0,
+                (size - 1) * size,
+            ),
+            None => search.research_path(&grid, 0, (size - 1) * size),
+        }.expect("the arrival is reachable");
+
+        total += start.elapsed();
+    }
+
+    println!(
+        "research {} x {} ({:?}{}): {:?} per research",
+        size,
+        size,
+        algorithm,
+        if jump_table { ", jump table" } else { "" },
+        total / RUNS,
+    );
+
+    total / RUNS
+}
+
+/// Builds the abstract graph of a walled grid, then researches its path
+/// several times and prints the average duration of one research.
+///
+/// # Arguments:
+///
+/// * `size` - the number of nodes per line and per column
+/// * `cluster_size` - the number of nodes per line and per column
+///   of a cluster
+fn bench_hierarchical_research(
+    size: usize,
+    cluster_size: usize,
+) {
+
+    let grid = create_walled_grid(size);
+
+    let start = Instant::now();
+    let hierarchy = HierarchicalGrid::new(&grid, cluster_size);
+    let build_duration = start.elapsed();
+
+    let mut total = Duration::from_secs(0);
+
+    for _ in 0..RUNS {
+
+        let start = Instant::now();
+
+        hierarchy.research_path(&grid, 0, (size - 1) * size)
+            .expect("the arrival is reachable");
+
+        total += start.elapsed();
+    }
+
+    /* a node is blocked in the middle of the grid */
+    let mut grid = grid;
+    let mut hierarchy = hierarchy;
+    let index = size / 4 * size + size / 2;
+
+    grid.get_node_by_index(index)
+        .set_unusuable();
+
+    let start = Instant::now();
+    hierarchy.notify_cell_changed(&grid, index)
+        .expect("the index is inside of the grid");
+    let update_duration = start.elapsed();
+
+    println!(
+        "research {} x {} (hierarchical, clusters of {}): {:?} per research, \
+{:?} to build, {:?} to update",
+        size,
+        size,
+        cluster_size,
+        total / RUNS,
+        build_duration,
+        update_duration,
+    );
+}
+
+fn main() {
+
+    let all_vectors = env::args()
+        .any(|argument| argument == "--all-vectors");
+
+    for size in [256, 1024].iter() {
+
+        let heap_duration =
+            bench_research(*size, SearchAlgorithm::AStar, false);
+
+        if *size <= 256 || all_vectors {
+            bench_vector_research(*size, heap_duration);
+        }
+
+        bench_research(*size, SearchAlgorithm::JumpPoint, false);
+        bench_research(*size, SearchAlgorithm::JumpPoint, true);
+        bench_hierarchical_research(*size, 32);
+    }
+
+    bench_research(2048, SearchAlgorithm::AStar, false);
+    bench_hierarchical_research(2048, 16);
+    bench_hierarchical_research(2048, 32);
+}
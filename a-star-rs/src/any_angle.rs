@@ -0,0 +1,243 @@
+//! Any-angle research (Theta*, Lazy Theta*): the parent of a node can be
+//! any node in line of sight, so the path is made of straight segments
+//! of any angle between the centres of the nodes.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use grid::Grid;
+use path_error::PathError;
+
+/// Variants of the any-angle research.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AnyAngleAlgorithm {
+
+    /// Checks the line of sight from the parent of the expanded node
+    /// to every child, before adding the child to the open list.
+    #[default]
+    ThetaStar,
+
+    /// Assumes the line of sight from the parent of the expanded node
+    /// to every child, and only checks it once the child is expanded:
+    /// far fewer checks, for paths almost as short.
+    LazyThetaStar,
+}
+
+/// Path made of straight segments between the centres of nodes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnyAnglePath {
+    waypoints: Vec<usize>,
+    length: f64,
+}
+
+impl AnyAnglePath {
+
+    /// Getter of the waypoints.
+    ///
+    /// # Returns:
+    ///
+    /// The indices of the nodes where the path turns, departure excluded,
+    /// the last one being the arrival.
+    pub fn get_waypoints(&self) -> &[usize] {
+        &self.waypoints
+    }
+
+    /// Getter of the length.
+    ///
+    /// # Returns:
+    ///
+    /// The euclidean length of the path from the departure,
+    /// the distance between two neighbour nodes being 1.
+    pub fn get_length(&self) -> f64 {
+        self.length
+    }
+}
+
+/// State of one node during the research.
+#[derive(Clone, Copy)]
+struct AnyAngleNode {
+    length: f64,
+    parent: usize,
+    closed: bool,
+}
+
+/// Researches a path from the departure to the arrival whose segments
+/// can have any angle. The segments only go through usuable nodes,
+/// and through the corners allowed by the movement model of the grid
+/// (see `Grid::has_line_of_sight`).
+///
+/// The length of the segments is euclidean: the move costs
+/// and the terrain weights of the grid are not taken into account.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid of the research
+/// * `departure` - the departure cell index
+/// * `arrival` - the arrival cell index
+/// * `algorithm` - the variant of the research
+///
+/// # Returns:
+///
+/// The waypoints and the length of the path,
+/// or the reason why there is no path.
+pub fn research_any_angle_path(
+    grid: &Grid,
+    departure: usize,
+    arrival: usize,
+    algorithm: AnyAngleAlgorithm,
+) -> Result<AnyAnglePath, PathError> {
+
+    let nodes_amount = grid.get_nodes_amount();
+
+    if departure >= nodes_amount || arrival >= nodes_amount {
+        return Err(PathError::OutOfBounds);
+    }
+
+    if !grid.get_node(departure).is_usuable() {
+        return Err(PathError::DepartureBlocked);
+    }
+
+    if !grid.get_node(arrival).is_usuable() {
+        return Err(PathError::ArrivalBlocked);
+    }
+
+    if departure == arrival {
+        return Err(PathError::DepartureIsArrival);
+    }
+
+    let mut nodes = vec![
+        AnyAngleNode {
+            length: f64::INFINITY,
+            parent: departure,
+            closed: false,
+        };
+        nodes_amount
+    ];
+
+    /* the positive floats are ordered like the integers of their bits */
+    let mut open_list = BinaryHeap::new();
+    let mut children = Vec::new();
+
+    nodes[departure].length = 0.0;
+    open_list.push(Reverse((
+        get_distance(grid, departure, arrival).to_bits(),
+        departure,
+    )));
+
+    while let Some(Reverse((_, current))) = open_list.pop() {
+
+        if nodes[current].closed {
+            continue;
+        }
+
+        nodes[current].closed = true;
+        grid.generate_children_list(current, &mut children);
+
+        /* the parent has been assumed in line of sight, else the node
+           gets the closed neighbour with the shortest path as parent */
+        if
+            algorithm == AnyAngleAlgorithm::LazyThetaStar &&
+            grid.has_line_of_sight(nodes[current].parent, current) != Ok(true)
+        {
+            let (length, parent) = children.iter()
+                .filter(|child| nodes[**child].closed)
+                .filter(|child| grid.get_node(**child).is_usuable())
+                .map(|child| (
+                    nodes[*child].length + get_distance(grid, *child, current),
+                    *child,
+                ))
+                .fold((f64::INFINITY, current), |best, candidate| {
+                    if candidate.0 < best.0 { candidate } else { best }
+                });
+
+            nodes[current].length = length;
+            nodes[current].parent = parent;
+        }
+
+        if current == arrival {
+            break;
+        }
+
+        let parent = nodes[current].parent;
+
+        for child in children.iter() {
+
+            let child = *child;
+
+            if nodes[child].closed || !grid.get_node(child).is_usuable() {
+                continue;
+            }
+
+            /* the segment from the parent skips the current node */
+            let (length, child_parent) = if
+                algorithm == AnyAngleAlgorithm::LazyThetaStar ||
+                grid.has_line_of_sight(parent, child) == Ok(true)
+            {
+                (
+                    nodes[parent].length + get_distance(grid, parent, child),
+                    parent,
+                )
+            } else {
+                (
+                    nodes[current].length + get_distance(grid, current, child),
+                    current,
+                )
+            };
+
+            if length >= nodes[child].length {
+                continue;
+            }
+
+            nodes[child].length = length;
+            nodes[child].parent = child_parent;
+
+            open_list.push(Reverse((
+                (length + get_distance(grid, child, arrival)).to_bits(),
+                child,
+            )));
+        }
+    }
+
+    if !nodes[arrival].closed {
+        return Err(PathError::Unreachable);
+    }
+
+    let mut waypoints = Vec::new();
+    let mut current = arrival;
+
+    while current != departure {
+        waypoints.push(current);
+        current = nodes[current].parent;
+    }
+
+    waypoints.reverse();
+
+    Ok(AnyAnglePath {
+        waypoints,
+        length: nodes[arrival].length,
+    })
+}
+
+/// Returns the euclidean distance between the centres of two nodes.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid of the nodes
+/// * `from` - the index of the first node
+/// * `to` - the index of the second node
+///
+/// # Returns:
+///
+/// The distance, the distance between two neighbour nodes being 1.
+fn get_distance(
+    grid: &Grid,
+    from: usize,
+    to: usize,
+) -> f64 {
+
+    let width = grid.get_width();
+    let horizontal = (from % width) as f64 - (to % width) as f64;
+    let vertical = (from / width) as f64 - (to / width) as f64;
+
+    (horizontal * horizontal + vertical * vertical).sqrt()
+}
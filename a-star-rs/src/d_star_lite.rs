@@ -0,0 +1,474 @@
+//! D* Lite: incremental research of the cheapest path to one goal,
+//! repaired after the changes of the grid instead of researched again.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::mem;
+
+use grid::Grid;
+use path_error::PathError;
+
+/// Cost of the nodes that cannot reach the goal.
+const INFINITE: u32 = u32::MAX;
+
+/// Priority of a node into the queue, the smallest one first.
+type Key = (u32, u32);
+
+/// State of one node for the planner.
+#[derive(Clone, Copy)]
+struct PlannerNode {
+    cost: u32,
+    expected_cost: u32,
+    queued_key: Option<Key>,
+}
+
+impl Default for PlannerNode {
+
+    fn default() -> PlannerNode {
+        PlannerNode {
+            cost: INFINITE,
+            expected_cost: INFINITE,
+            queued_key: None,
+        }
+    }
+}
+
+/// Planner that researches the cheapest path from the position
+/// of a unit to a goal, and keeps its state between the researches:
+/// after a node of the grid is blocked, unblocked or weighted,
+/// only the costs affected by the change are computed again.
+///
+/// The costs are computed from the goal, so the unit can move
+/// along the path and research it again from its new position.
+/// The heuristic of the grid must be consistent
+/// (the default heuristics are).
+#[derive(Clone)]
+pub struct DStarLite {
+    nodes: Vec<PlannerNode>,
+    queue: BinaryHeap<Reverse<(u32, u32, usize)>>,
+    start_index: usize,
+    last_start_index: usize,
+    goal_index: usize,
+    key_modifier: u32,
+    expanded_amount: usize,
+    children_list: Vec<usize>,
+}
+
+impl DStarLite {
+
+    /// Constructor for a new DStarLite object, no cost is computed yet.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the researches
+    /// * `start` - the index of the unit position
+    /// * `goal` - the index of the goal
+    ///
+    /// # Returns:
+    ///
+    /// Created DStarLite object, or an error if an index
+    /// is outside of the grid.
+    pub fn new(
+        grid: &Grid,
+        start: usize,
+        goal: usize,
+    ) -> Result<DStarLite, PathError> {
+
+        let nodes_amount = grid.get_nodes_amount();
+
+        if start >= nodes_amount || goal >= nodes_amount {
+            return Err(PathError::OutOfBounds);
+        }
+
+        let mut planner = DStarLite {
+            nodes: vec![PlannerNode::default(); nodes_amount],
+            queue: BinaryHeap::new(),
+            start_index: start,
+            last_start_index: start,
+            goal_index: goal,
+            key_modifier: 0,
+            expanded_amount: 0,
+            children_list: Vec::new(),
+        };
+
+        planner.nodes[goal].expected_cost = 0;
+
+        let key = planner.calculate_key(grid, goal);
+        planner.push(goal, key);
+
+        Ok(planner)
+    }
+
+    /// Getter of the start index.
+    ///
+    /// # Returns:
+    ///
+    /// The index of the unit position.
+    pub fn get_start_index(&self) -> usize {
+        self.start_index
+    }
+
+    /// Getter of the goal index.
+    ///
+    /// # Returns:
+    ///
+    /// The index of the goal.
+    pub fn get_goal_index(&self) -> usize {
+        self.goal_index
+    }
+
+    /// Moves the unit, the next research starts from its new position.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the researches
+    /// * `start` - the index of the new unit position
+    ///
+    /// # Returns:
+    ///
+    /// An error if the index is outside of the grid.
+    pub fn set_start_index(
+        &mut self,
+        grid: &Grid,
+        start: usize,
+    ) -> Result<(), PathError> {
+
+        if start >= self.nodes.len() {
+            return Err(PathError::OutOfBounds);
+        }
+
+        /* the keys of the queue stay lower bounds of the new keys */
+        self.key_modifier = self.key_modifier.saturating_add(
+            grid.estimate_heuristic(self.last_start_index, start),
+        );
+        self.last_start_index = start;
+        self.start_index = start;
+
+        Ok(())
+    }
+
+    /// Takes into account a change of one node of the grid:
+    /// blocked, unblocked or weighted. Must be called after every
+    /// change, before the next research.
+    ///
+    /// The movements from the node, to the node and the diagonal
+    /// movements around it (that may depend on it) are updated.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the modified grid
+    /// * `index` - the index of the modified node
+    ///
+    /// # Returns:
+    ///
+    /// An error if the index is outside of the grid.
+    pub fn notify_cell_changed(
+        &mut self,
+        grid: &Grid,
+        index: usize,
+    ) -> Result<(), PathError> {
+
+        if index >= self.nodes.len() || index >= grid.get_nodes_amount() {
+            return Err(PathError::OutOfBounds);
+        }
+
+        let width = grid.get_width();
+        let (x, y) = (index % width, index / width);
+
+        let columns = x.saturating_sub(1)..(x + 2).min(width);
+        let lines = y.saturating_sub(1)..(y + 2).min(grid.get_height());
+
+        for line in lines {
+            for column in columns.clone() {
+                self.update_node(grid, line * width + column);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Repairs the costs affected by the changes since the last research,
+    /// then returns the cheapest path from the unit position to the goal.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid the planner has been created for
+    ///
+    /// # Returns:
+    ///
+    /// The indices of the path nodes, unit position excluded,
+    /// or the reason why there is no path.
+    pub fn research_path(
+        &mut self,
+        grid: &Grid,
+    ) -> Result<Vec<usize>, PathError> {
+
+        if grid.get_nodes_amount() != self.nodes.len() {
+            return Err(PathError::OutOfBounds);
+        }
+
+        if !grid.get_node(self.start_index).is_usuable() {
+            return Err(PathError::DepartureBlocked);
+        }
+
+        if !grid.get_node(self.goal_index).is_usuable() {
+            return Err(PathError::ArrivalBlocked);
+        }
+
+        if self.start_index == self.goal_index {
+            return Err(PathError::DepartureIsArrival);
+        }
+
+        self.expanded_amount = 0;
+        self.compute_costs(grid);
+
+        if self.nodes[self.start_index].cost == INFINITE {
+            return Err(PathError::Unreachable);
+        }
+
+        let mut path = Vec::new();
+        let mut current_index = self.start_index;
+
+        while current_index != self.goal_index {
+
+            /* the costs decrease strictly along the path,
+               so it cannot be longer than the amount of nodes */
+            if path.len() == self.nodes.len() {
+                return Err(PathError::Unreachable);
+            }
+
+            current_index = match self.get_cheapest_child(grid, current_index) {
+                Some((_, index)) => index,
+                None => return Err(PathError::Unreachable),
+            };
+
+            path.push(current_index);
+        }
+
+        Ok(path)
+    }
+
+    /// Returns the cost of the cheapest path from one node to the goal,
+    /// computed by the last research.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The cost, `u32::MAX` if unknown or if the goal cannot be reached.
+    pub fn get_cost(
+        &self,
+        index: usize,
+    ) -> u32 {
+        self.nodes[index].cost
+    }
+
+    /// Returns the amount of nodes whose cost has been computed
+    /// by the last research.
+    ///
+    /// # Returns:
+    ///
+    /// The amount of expanded nodes.
+    pub fn get_expanded_amount(&self) -> usize {
+        self.expanded_amount
+    }
+
+    /// Computes the costs of the queued nodes until the cost
+    /// of the unit position is known.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    fn compute_costs(
+        &mut self,
+        grid: &Grid,
+    ) {
+
+        let start = self.start_index;
+
+        loop {
+
+            let (old_key, index) = match self.get_top() {
+                Some(top) => top,
+                None => return,
+            };
+
+            let start_node = self.nodes[start];
+
+            if
+                old_key >= self.calculate_key(grid, start) &&
+                start_node.expected_cost == start_node.cost
+            {
+                return;
+            }
+
+            let new_key = self.calculate_key(grid, index);
+
+            if old_key < new_key {
+                self.push(index, new_key);
+                continue;
+            }
+
+            self.queue.pop();
+            self.nodes[index].queued_key = None;
+            self.expanded_amount += 1;
+
+            let node = self.nodes[index];
+
+            if node.cost > node.expected_cost {
+                self.nodes[index].cost = node.expected_cost;
+            } else {
+                self.nodes[index].cost = INFINITE;
+                self.update_node(grid, index);
+            }
+
+            let mut children = mem::take(&mut self.children_list);
+            grid.generate_children_list(index, &mut children);
+
+            /* the neighbours are the nodes that can move to this one */
+            for child in children.iter() {
+                self.update_node(grid, *child);
+            }
+
+            self.children_list = children;
+        }
+    }
+
+    /// Computes again the expected cost of one node from the costs
+    /// of its children, and queues the node if its cost is outdated.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    /// * `index` - the index of the node
+    fn update_node(
+        &mut self,
+        grid: &Grid,
+        index: usize,
+    ) {
+
+        if index != self.goal_index {
+            self.nodes[index].expected_cost =
+                match self.get_cheapest_child(grid, index) {
+                    Some((cost, _)) => cost,
+                    None => INFINITE,
+                };
+        }
+
+        let node = self.nodes[index];
+
+        if node.cost == node.expected_cost {
+            self.nodes[index].queued_key = None;
+            return;
+        }
+
+        let key = self.calculate_key(grid, index);
+        self.push(index, key);
+    }
+
+    /// Returns the child of one node with the cheapest path to the goal.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The cost of the path through the child and the child index,
+    /// or None if no child can reach the goal.
+    fn get_cheapest_child(
+        &mut self,
+        grid: &Grid,
+        index: usize,
+    ) -> Option<(u32, usize)> {
+
+        if !grid.get_node(index).is_usuable() {
+            return None;
+        }
+
+        let mut children = mem::take(&mut self.children_list);
+        grid.generate_children_list(index, &mut children);
+
+        let cheapest = children.iter()
+            .filter(|child| grid.get_node(**child).is_usuable())
+            .filter(|child| self.nodes[**child].cost != INFINITE)
+            .map(|child| (
+                self.nodes[*child].cost
+                    .saturating_add(grid.get_movement_cost(index, *child)),
+                *child,
+            ))
+            .min();
+
+        self.children_list = children;
+
+        cheapest
+    }
+
+    /// Computes the priority of one node into the queue.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The estimated cost of the path from the unit position
+    /// through the node, then the cost of the node.
+    fn calculate_key(
+        &self,
+        grid: &Grid,
+        index: usize,
+    ) -> Key {
+
+        let node = self.nodes[index];
+        let cost = node.cost.min(node.expected_cost);
+
+        (
+            cost
+                .saturating_add(grid.estimate_heuristic(self.start_index, index))
+                .saturating_add(self.key_modifier),
+            cost,
+        )
+    }
+
+    /// Queues one node with a new priority, its previous entry
+    /// is ignored from now on.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    /// * `key` - the priority of the node
+    fn push(
+        &mut self,
+        index: usize,
+        key: Key,
+    ) {
+        self.nodes[index].queued_key = Some(key);
+        self.queue.push(Reverse((key.0, key.1, index)));
+    }
+
+    /// Returns the queued node with the smallest priority,
+    /// the outdated entries are removed first.
+    ///
+    /// # Returns:
+    ///
+    /// The priority and the index of the node, or None if the queue
+    /// is empty.
+    fn get_top(&mut self) -> Option<(Key, usize)> {
+
+        while let Some(&Reverse((first, second, index))) = self.queue.peek() {
+
+            if self.nodes[index].queued_key == Some((first, second)) {
+                return Some(((first, second), index));
+            }
+
+            self.queue.pop();
+        }
+
+        None
+    }
+}
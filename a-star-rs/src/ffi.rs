@@ -0,0 +1,1063 @@
+//! C interface of the library, declared into `astar.h`.
+//!
+//! Every function checks the lengths of the given arrays: nothing is read
+//! or written outside of them. The functions that return a path write
+//! its indices into a buffer supplied by the caller and return
+//! the amount of written indices, or a negative error code.
+
+use std::convert::TryFrom;
+use std::ptr;
+use std::slice;
+
+use libc::{
+    c_int,
+    ptrdiff_t,
+    size_t,
+};
+
+use flow_field::FlowField;
+use grid::Grid;
+use move_costs::MoveCosts;
+use movement::Movement;
+use path_error::PathError;
+use search_algorithm::SearchAlgorithm;
+use search_context::SearchContext;
+
+/// Status code returned by `get_path` when the path has been found.
+pub const PATH_FOUND: c_int = 0;
+
+/// Status code returned by `get_path` when the path does not fit
+/// into the given C array.
+pub const PATH_BUFFER_TOO_SMALL: c_int = 6;
+
+/// Error code returned when the arrival cannot be reached.
+pub const ASTAR_ERROR_UNREACHABLE: ptrdiff_t = -1;
+
+/// Error code returned when the departure is unusuable.
+pub const ASTAR_ERROR_DEPARTURE_BLOCKED: ptrdiff_t = -2;
+
+/// Error code returned when the arrival is unusuable.
+pub const ASTAR_ERROR_ARRIVAL_BLOCKED: ptrdiff_t = -3;
+
+/// Error code returned when an index is outside of the grid.
+pub const ASTAR_ERROR_OUT_OF_BOUNDS: ptrdiff_t = -4;
+
+/// Error code returned when the departure is the arrival.
+pub const ASTAR_ERROR_DEPARTURE_IS_ARRIVAL: ptrdiff_t = -5;
+
+/// Error code returned when the path does not fit into the buffer.
+pub const ASTAR_ERROR_BUFFER_TOO_SMALL: ptrdiff_t = -6;
+
+/// Error code returned when a null pointer is given with a non zero length.
+pub const ASTAR_ERROR_NULL_POINTER: ptrdiff_t = -7;
+
+/// Error code returned when the cost of a path does not fit into 32 bits.
+pub const ASTAR_ERROR_COST_OVERFLOW: ptrdiff_t = -10;
+
+/// Distance written by `astar_grid_flow_field` for the nodes
+/// that cannot reach any goal.
+pub const ASTAR_UNREACHABLE_DISTANCE: u32 = 0xFFFFFFFF;
+
+/// Direction written by `astar_grid_flow_field` for the goals
+/// and for the nodes that cannot reach any goal.
+pub const ASTAR_DIRECTION_NONE: i8 = -1;
+
+/// Direction of a movement to the right.
+pub const ASTAR_DIRECTION_RIGHT: i8 = 0;
+
+/// Direction of a movement to the bottom right.
+pub const ASTAR_DIRECTION_BOTTOM_RIGHT: i8 = 1;
+
+/// Direction of a movement to the bottom.
+pub const ASTAR_DIRECTION_BOTTOM: i8 = 2;
+
+/// Direction of a movement to the bottom left.
+pub const ASTAR_DIRECTION_BOTTOM_LEFT: i8 = 3;
+
+/// Direction of a movement to the left.
+pub const ASTAR_DIRECTION_LEFT: i8 = 4;
+
+/// Direction of a movement to the top left.
+pub const ASTAR_DIRECTION_TOP_LEFT: i8 = 5;
+
+/// Direction of a movement to the top.
+pub const ASTAR_DIRECTION_TOP: i8 = 6;
+
+/// Direction of a movement to the top right.
+pub const ASTAR_DIRECTION_TOP_RIGHT: i8 = 7;
+
+/// Horizontal and vertical movements only.
+pub const ASTAR_MOVEMENT_FOUR_DIRECTIONS: u32 = 0;
+
+/// Diagonal movements allowed, even between two unusuable nodes.
+pub const ASTAR_MOVEMENT_EIGHT_DIRECTIONS: u32 = 1;
+
+/// Diagonal movements forbidden next to an unusuable node.
+pub const ASTAR_MOVEMENT_NO_CORNER_CUTTING: u32 = 2;
+
+/// Diagonal movements forbidden between two unusuable nodes.
+pub const ASTAR_MOVEMENT_NO_SQUEEZING: u32 = 3;
+
+/// A* research, the default one.
+pub const ASTAR_ALGORITHM_ASTAR: u32 = 0;
+
+/// Dijkstra research, without heuristic.
+pub const ASTAR_ALGORITHM_DIJKSTRA: u32 = 1;
+
+/// Breadth first research, the path with the fewest nodes.
+pub const ASTAR_ALGORITHM_BREADTH_FIRST: u32 = 2;
+
+/// Greedy best first research, by heuristic only.
+pub const ASTAR_ALGORITHM_GREEDY_BEST_FIRST: u32 = 3;
+
+/// Jump point research, on grids with uniform terrain weights.
+pub const ASTAR_ALGORITHM_JUMP_POINT: u32 = 4;
+
+/// Bidirectional A* research.
+pub const ASTAR_ALGORITHM_BIDIRECTIONAL: u32 = 5;
+
+/// Grid kept between the researches of the handle functions,
+/// opaque for the C callers.
+pub struct GridHandle {
+    grid: Grid,
+    search: SearchContext,
+}
+
+/// Horizontal and vertical positions of a node, with a C layout.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub horizontal: u32,
+    pub vertical: u32,
+}
+
+/// Cost, length and research statistics of a path, with a C layout.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AstarPathResult {
+    pub cost: u32,
+    pub length: f64,
+    pub expanded_amount: size_t,
+    pub generated_amount: size_t,
+    pub peak_open_amount: size_t,
+    pub closed_amount: size_t,
+}
+
+/// Returns the error code of a path error for the C interface.
+///
+/// # Arguments:
+///
+/// * `error` - the error of the research
+///
+/// # Returns:
+///
+/// The negative error code.
+fn get_error_code(error: PathError) -> ptrdiff_t {
+    -(error.get_status_code() as ptrdiff_t)
+}
+
+/// Returns the amount of nodes of a grid.
+///
+/// # Arguments:
+///
+/// * `width` - the number of nodes per line
+/// * `height` - the number of nodes per column
+///
+/// # Returns:
+///
+/// The amount of nodes, or None if the node indices
+/// do not fit into 32 bits.
+fn get_nodes_amount(
+    width: u32,
+    height: u32,
+) -> Option<usize> {
+
+    let nodes_amount = (width as u64) * (height as u64);
+
+    if nodes_amount > u32::MAX as u64 {
+        return None;
+    }
+
+    Some(nodes_amount as usize)
+}
+
+/// Builds a slice from a C array, accepting a null pointer
+/// only if the array is empty.
+///
+/// # Arguments:
+///
+/// * `pointer` - C pointer to the first item
+/// * `length` - the amount of items
+///
+/// # Returns:
+///
+/// The slice, or None if the pointer is null and the length is not zero.
+unsafe fn get_slice<'a, T>(
+    pointer: *const T,
+    length: size_t,
+) -> Option<&'a [T]> {
+
+    if pointer.is_null() {
+        return if length == 0 { Some(&[]) } else { None };
+    }
+
+    Some(slice::from_raw_parts(pointer, length))
+}
+
+/// Builds a mutable slice from a C array, accepting a null pointer
+/// only if the array is empty.
+///
+/// # Arguments:
+///
+/// * `pointer` - C pointer to the first item
+/// * `length` - the amount of items
+///
+/// # Returns:
+///
+/// The slice, or None if the pointer is null and the length is not zero.
+unsafe fn get_slice_mut<'a, T>(
+    pointer: *mut T,
+    length: size_t,
+) -> Option<&'a mut [T]> {
+
+    if pointer.is_null() {
+        return if length == 0 { Some(&mut []) } else { None };
+    }
+
+    Some(slice::from_raw_parts_mut(pointer, length))
+}
+
+/// Researches the path on a grid described by C arrays
+/// and writes it into the given buffer.
+///
+/// # Arguments:
+///
+/// * `path` - the buffer the path indices are written into
+/// * `walls` - the unusuable node indices
+/// * `weights` - the terrain weights (one per node), if any
+/// * `width` - the number of nodes per line
+/// * `height` - the number of nodes per column
+/// * `departure` - the departure node index
+/// * `arrival` - the arrival node index
+/// * `costs` - the movement costs
+///
+/// # Returns:
+///
+/// The amount of written indices, or the negative error code.
+#[allow(clippy::too_many_arguments)]
+fn write_path(
+    path: &mut [u32],
+    walls: &[u32],
+    weights: Option<&[u8]>,
+    width: u32,
+    height: u32,
+    departure: u32,
+    arrival: u32,
+    costs: MoveCosts,
+) -> ptrdiff_t {
+
+    if get_nodes_amount(width, height).is_none() {
+        return ASTAR_ERROR_OUT_OF_BOUNDS;
+    }
+
+    let mut grid = Grid::new(
+        width as usize,
+        height as usize,
+    );
+
+    grid.set_move_costs(costs);
+
+    if let Some(weights) = weights {
+        if let Err(error) = grid.set_weights(weights) {
+            return get_error_code(error);
+        }
+    }
+
+    for wall in walls {
+
+        let wall = *wall as usize;
+
+        if wall >= grid.get_nodes_amount() {
+            return ASTAR_ERROR_OUT_OF_BOUNDS;
+        }
+
+        grid.get_node_by_index(wall)
+            .set_unusuable();
+    }
+
+    let path_indices = match SearchContext::new().research_path(
+        &grid,
+        departure as usize,
+        arrival as usize,
+    ) {
+        Ok(path_indices) => path_indices,
+        Err(error) => return get_error_code(error),
+    };
+
+    copy_path(path, &path_indices)
+}
+
+/// Copies the path indices into the C array, if they all fit.
+///
+/// # Arguments:
+///
+/// * `path` - the buffer the path indices are written into
+/// * `path_indices` - the indices of the path
+///
+/// # Returns:
+///
+/// The amount of written indices, `ASTAR_ERROR_BUFFER_TOO_SMALL`,
+/// or `ASTAR_ERROR_OUT_OF_BOUNDS` if an index does not fit into 32 bits.
+fn copy_path(
+    path: &mut [u32],
+    path_indices: &[usize],
+) -> ptrdiff_t {
+
+    if path_indices.len() > path.len() {
+        return ASTAR_ERROR_BUFFER_TOO_SMALL;
+    }
+
+    for (counter, index) in path_indices.iter().enumerate() {
+        path[counter] = match u32::try_from(*index) {
+            Ok(index) => index,
+            Err(_) => return ASTAR_ERROR_OUT_OF_BOUNDS,
+        };
+    }
+
+    path_indices.len() as ptrdiff_t
+}
+
+/// Calculates and stores the path into the given C array.
+///
+/// # Arguments:
+///
+/// * `path` - C pointer to the array the path indices are written into
+///   (departure excluded, arrival included)
+/// * `path_capacity` - the amount of indices the path array can contain
+/// * `walls` - C pointer to the array of unusuable node indices
+/// * `walls_amount` - the amount of indices into the walls array
+/// * `weights` - C pointer to the array of terrain weights (one per node,
+///   0 for unusuable nodes), or null if every node has a weight of 1
+/// * `width` - the number of nodes per line
+/// * `height` - the number of nodes per column
+/// * `departure` - the departure node index
+/// * `arrival` - the arrival node index
+///
+/// # Returns:
+///
+/// The amount of written indices, otherwise a negative `ASTAR_ERROR_*` code.
+/// Nothing is written if the path does not fit into the array; a path
+/// never contains more than `width * height - 1` indices.
+///
+/// # Safety
+///
+/// `path` and `walls` must point to at least `path_capacity`
+/// and `walls_amount` items (or be null if their length is 0),
+/// `weights` must be null or point to `width * height` weights.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn astar_find_path(
+    path: *mut u32,
+    path_capacity: size_t,
+    walls: *const u32,
+    walls_amount: size_t,
+    weights: *const u8,
+    width: u32,
+    height: u32,
+    departure: u32,
+    arrival: u32,
+) -> ptrdiff_t {
+
+    let costs = MoveCosts::default();
+
+    astar_find_path_with_costs(
+        path,
+        path_capacity,
+        walls,
+        walls_amount,
+        weights,
+        width,
+        height,
+        departure,
+        arrival,
+        costs.get_horizontal_or_vertical(),
+        costs.get_diagonal(),
+    )
+}
+
+/// Calculates and stores the path into the given C array,
+/// using the given movement costs instead of the default ones (10 and 14).
+///
+/// # Arguments:
+///
+/// * the same arguments as `astar_find_path`
+/// * `horizontal_or_vertical_cost` - the cost of a horizontal
+///   or vertical movement
+/// * `diagonal_cost` - the cost of a diagonal movement
+///
+/// # Returns:
+///
+/// The same values as `astar_find_path`.
+///
+/// # Safety
+///
+/// The same requirements as `astar_find_path`.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn astar_find_path_with_costs(
+    path: *mut u32,
+    path_capacity: size_t,
+    walls: *const u32,
+    walls_amount: size_t,
+    weights: *const u8,
+    width: u32,
+    height: u32,
+    departure: u32,
+    arrival: u32,
+    horizontal_or_vertical_cost: u32,
+    diagonal_cost: u32,
+) -> ptrdiff_t {
+
+    let path = match get_slice_mut(path, path_capacity) {
+        Some(path) => path,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let walls = match get_slice(walls, walls_amount) {
+        Some(walls) => walls,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let nodes_amount = match get_nodes_amount(width, height) {
+        Some(nodes_amount) => nodes_amount,
+        None => return ASTAR_ERROR_OUT_OF_BOUNDS,
+    };
+
+    let weights = if weights.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(
+            weights,
+            nodes_amount,
+        ))
+    };
+
+    write_path(
+        path,
+        walls,
+        weights,
+        width,
+        height,
+        departure,
+        arrival,
+        MoveCosts::new(
+            horizontal_or_vertical_cost,
+            diagonal_cost,
+        ),
+    )
+}
+
+/// Returns the positions of an index according to the width.
+///
+/// # Arguments:
+///
+/// * `width` - the width of the map, must not be 0
+/// * `index` - the source index
+///
+/// # Returns:
+///
+/// The horizontal and vertical positions, or `{ 0, 0 }` if the width is 0.
+#[no_mangle]
+pub extern "C" fn astar_get_position(
+    width: u32,
+    index: u32,
+) -> Position {
+
+    if width == 0 {
+        return Position {
+            horizontal: 0,
+            vertical: 0,
+        };
+    }
+
+    Position {
+        horizontal: index % width,
+        vertical: index / width,
+    }
+}
+
+/// Creates a grid that can be used for many researches.
+/// Every node is usuable, with a terrain weight of 1.
+///
+/// # Arguments:
+///
+/// * `width` - the number of nodes per line
+/// * `height` - the number of nodes per column
+///
+/// # Returns:
+///
+/// The grid handle, to be released with `astar_grid_free`,
+/// or null if `width * height` does not fit into 32 bits.
+#[no_mangle]
+pub extern "C" fn astar_grid_new(
+    width: u32,
+    height: u32,
+) -> *mut GridHandle {
+
+    if get_nodes_amount(width, height).is_none() {
+        return ptr::null_mut();
+    }
+
+    let handle = GridHandle {
+        grid: Grid::new(
+            width as usize,
+            height as usize,
+        ),
+        search: SearchContext::new(),
+    };
+
+    Box::into_raw(Box::new(handle))
+}
+
+/// Sets one node of the grid as blocked or usuable.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid handle
+/// * `index` - the index of the node
+/// * `blocked` - true if the node cannot be part of a path
+///
+/// # Returns:
+///
+/// 0, or a negative `ASTAR_ERROR_*` code.
+///
+/// # Safety
+///
+/// `grid` must be null or returned by `astar_grid_new` and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn astar_grid_set_blocked(
+    grid: *mut GridHandle,
+    index: u32,
+    blocked: bool,
+) -> ptrdiff_t {
+
+    let handle = match grid.as_mut() {
+        Some(handle) => handle,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    match handle.grid.set_blocked(index as usize, blocked) {
+        Ok(()) => 0,
+        Err(error) => get_error_code(error),
+    }
+}
+
+/// Sets the terrain weight of one node of the grid.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid handle
+/// * `index` - the index of the node
+/// * `cost` - the terrain weight, the movement cost to the node
+///   is multiplied by this value; 0 sets the node as blocked
+///
+/// # Returns:
+///
+/// 0, or a negative `ASTAR_ERROR_*` code.
+///
+/// # Safety
+///
+/// `grid` must be null or returned by `astar_grid_new` and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn astar_grid_set_cost(
+    grid: *mut GridHandle,
+    index: u32,
+    cost: u8,
+) -> ptrdiff_t {
+
+    if cost == 0 {
+        return astar_grid_set_blocked(
+            grid,
+            index,
+            true,
+        );
+    }
+
+    let handle = match grid.as_mut() {
+        Some(handle) => handle,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    match handle.grid.set_weight(index as usize, cost) {
+        Ok(()) => 0,
+        Err(error) => get_error_code(error),
+    }
+}
+
+/// Sets the movement model of the grid, that defines the children
+/// of every node. All the eight directions are allowed by default.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid handle
+/// * `movement` - one of the `ASTAR_MOVEMENT_*` values
+///
+/// # Returns:
+///
+/// 0, or a negative `ASTAR_ERROR_*` code
+/// (`ASTAR_ERROR_OUT_OF_BOUNDS` for an unknown movement model).
+///
+/// # Safety
+///
+/// `grid` must be null or returned by `astar_grid_new` and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn astar_grid_set_movement(
+    grid: *mut GridHandle,
+    movement: u32,
+) -> ptrdiff_t {
+
+    let handle = match grid.as_mut() {
+        Some(handle) => handle,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let movement = match movement {
+        ASTAR_MOVEMENT_FOUR_DIRECTIONS => Movement::FourDirections,
+        ASTAR_MOVEMENT_EIGHT_DIRECTIONS => Movement::EightDirections,
+        ASTAR_MOVEMENT_NO_CORNER_CUTTING =>
+            Movement::EightDirectionsNoCornerCutting,
+        ASTAR_MOVEMENT_NO_SQUEEZING => Movement::EightDirectionsNoSqueezing,
+        _ => return ASTAR_ERROR_OUT_OF_BOUNDS,
+    };
+
+    handle.grid.set_movement(movement);
+    0
+}
+
+/// Sets the base costs of the movements of the grid
+/// (10 and 14 by default).
+///
+/// # Arguments:
+///
+/// * `grid` - the grid handle
+/// * `horizontal_or_vertical_cost` - the cost of a horizontal
+///   or vertical movement
+/// * `diagonal_cost` - the cost of a diagonal movement
+///
+/// # Returns:
+///
+/// 0, or a negative `ASTAR_ERROR_*` code.
+///
+/// # Safety
+///
+/// `grid` must be null or returned by `astar_grid_new` and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn astar_grid_set_move_costs(
+    grid: *mut GridHandle,
+    horizontal_or_vertical_cost: u32,
+    diagonal_cost: u32,
+) -> ptrdiff_t {
+
+    let handle = match grid.as_mut() {
+        Some(handle) => handle,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    handle.grid.set_move_costs(MoveCosts::new(
+        horizontal_or_vertical_cost,
+        diagonal_cost,
+    ));
+    0
+}
+
+/// Sets the algorithm of the next researches of the grid
+/// (A* by default).
+///
+/// # Arguments:
+///
+/// * `grid` - the grid handle
+/// * `algorithm` - one of the `ASTAR_ALGORITHM_*` values
+///
+/// # Returns:
+///
+/// 0, or a negative `ASTAR_ERROR_*` code
+/// (`ASTAR_ERROR_OUT_OF_BOUNDS` for an unknown algorithm).
+///
+/// # Safety
+///
+/// `grid` must be null or returned by `astar_grid_new` and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn astar_grid_set_algorithm(
+    grid: *mut GridHandle,
+    algorithm: u32,
+) -> ptrdiff_t {
+
+    let handle = match grid.as_mut() {
+        Some(handle) => handle,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let algorithm = match algorithm {
+        ASTAR_ALGORITHM_ASTAR => SearchAlgorithm::AStar,
+        ASTAR_ALGORITHM_DIJKSTRA => SearchAlgorithm::Dijkstra,
+        ASTAR_ALGORITHM_BREADTH_FIRST => SearchAlgorithm::BreadthFirst,
+        ASTAR_ALGORITHM_GREEDY_BEST_FIRST => SearchAlgorithm::GreedyBestFirst,
+        ASTAR_ALGORITHM_JUMP_POINT => SearchAlgorithm::JumpPoint,
+        ASTAR_ALGORITHM_BIDIRECTIONAL => SearchAlgorithm::Bidirectional,
+        _ => return ASTAR_ERROR_OUT_OF_BOUNDS,
+    };
+
+    handle.search.set_algorithm(algorithm);
+    0
+}
+
+/// Researches the path between two nodes of the grid and writes it
+/// into the given C array. The memory used by the research is kept
+/// into the grid for the next ones.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid handle
+/// * `departure` - the departure node index
+/// * `arrival` - the arrival node index
+/// * `path` - C pointer to the array the path indices are written into
+///   (departure excluded, arrival included)
+/// * `path_capacity` - the amount of indices the path array can contain
+///
+/// # Returns:
+///
+/// The same values as `astar_find_path`.
+///
+/// # Safety
+///
+/// `grid` must be null or returned by `astar_grid_new` and not freed,
+/// `path` must point to at least `path_capacity` items
+/// (or be null if `path_capacity` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn astar_grid_find_path(
+    grid: *mut GridHandle,
+    departure: u32,
+    arrival: u32,
+    path: *mut u32,
+    path_capacity: size_t,
+) -> ptrdiff_t {
+
+    let handle = match grid.as_mut() {
+        Some(handle) => handle,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let path = match get_slice_mut(path, path_capacity) {
+        Some(path) => path,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let path_indices = match handle.search.research_path(
+        &handle.grid,
+        departure as usize,
+        arrival as usize,
+    ) {
+        Ok(path_indices) => path_indices,
+        Err(error) => return get_error_code(error),
+    };
+
+    copy_path(path, &path_indices)
+}
+
+/// Researches the path between two nodes of the grid like
+/// `astar_grid_find_path`, and writes its cost, its length
+/// and the statistics of the research into the given result.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid handle
+/// * `departure` - the departure node index
+/// * `arrival` - the arrival node index
+/// * `path` - C pointer to the array the path indices are written into
+///   (departure excluded, arrival included)
+/// * `path_capacity` - the amount of indices the path array can contain
+/// * `closed` - C pointer to the array the closed node indices
+///   are written into, sorted in ascending order
+/// * `closed_capacity` - the amount of indices the closed array
+///   can contain, 0 if the closed list is not requested
+/// * `result` - C pointer to the result written
+///   when the path has been found, required
+///
+/// # Returns:
+///
+/// The same values as `astar_find_path`. Nothing is written if the path
+/// or the requested closed list does not fit into its array.
+///
+/// # Safety
+///
+/// `grid` must be null or returned by `astar_grid_new` and not freed,
+/// `path` and `closed` must point to at least `path_capacity`
+/// and `closed_capacity` items (or be null if their length is 0),
+/// `result` must point to a result (null is rejected
+/// with `ASTAR_ERROR_NULL_POINTER`).
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn astar_grid_find_path_result(
+    grid: *mut GridHandle,
+    departure: u32,
+    arrival: u32,
+    path: *mut u32,
+    path_capacity: size_t,
+    closed: *mut u32,
+    closed_capacity: size_t,
+    result: *mut AstarPathResult,
+) -> ptrdiff_t {
+
+    let handle = match grid.as_mut() {
+        Some(handle) => handle,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let path = match get_slice_mut(path, path_capacity) {
+        Some(path) => path,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let closed = match get_slice_mut(closed, closed_capacity) {
+        Some(closed) => closed,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let result = match result.as_mut() {
+        Some(result) => result,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let path_result = match handle.search.research_path_result(
+        &handle.grid,
+        departure as usize,
+        arrival as usize,
+        closed_capacity > 0,
+    ) {
+        Ok(path_result) => path_result,
+        Err(error) => return get_error_code(error),
+    };
+
+    let closed_list = path_result.get_closed_list()
+        .unwrap_or(&[]);
+
+    if path_result.get_path().len() > path.len() {
+        return ASTAR_ERROR_BUFFER_TOO_SMALL;
+    }
+
+    if !closed.is_empty() {
+
+        let status = copy_path(closed, closed_list);
+
+        if status < 0 {
+            return status;
+        }
+    }
+
+    *result = AstarPathResult {
+        cost: path_result.get_cost(),
+        length: path_result.get_length(),
+        expanded_amount: path_result.get_expanded_amount(),
+        generated_amount: path_result.get_generated_amount(),
+        peak_open_amount: path_result.get_peak_open_amount(),
+        closed_amount: closed_list.len(),
+    };
+
+    copy_path(path, path_result.get_path())
+}
+
+/// Generates the distance to the nearest goal and the direction
+/// of the next movement for every node of the grid.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid handle
+/// * `goals` - C pointer to the array of goal indices,
+///   the blocked ones are ignored
+/// * `goals_amount` - the amount of indices into the goals array
+/// * `distances` - C pointer to the array the distances are written into,
+///   `ASTAR_UNREACHABLE_DISTANCE` if no goal can be reached
+/// * `directions` - C pointer to the array the directions are written into,
+///   `ASTAR_DIRECTION_NONE` for the goals and if no goal can be reached
+/// * `nodes_amount` - the amount of items the distances and directions
+///   arrays can contain, at least `width * height`
+///
+/// # Returns:
+///
+/// 0, or a negative `ASTAR_ERROR_*` code.
+///
+/// # Safety
+///
+/// `grid` must be null or returned by `astar_grid_new` and not freed,
+/// `goals` must point to at least `goals_amount` items,
+/// `distances` and `directions` to at least `nodes_amount` items
+/// (or be null if their length is 0).
+#[no_mangle]
+pub unsafe extern "C" fn astar_grid_flow_field(
+    grid: *mut GridHandle,
+    goals: *const u32,
+    goals_amount: size_t,
+    distances: *mut u32,
+    directions: *mut i8,
+    nodes_amount: size_t,
+) -> ptrdiff_t {
+
+    let handle = match grid.as_mut() {
+        Some(handle) => handle,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let goals = match get_slice(goals, goals_amount) {
+        Some(goals) => goals,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let distances = match get_slice_mut(distances, nodes_amount) {
+        Some(distances) => distances,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let directions = match get_slice_mut(directions, nodes_amount) {
+        Some(directions) => directions,
+        None => return ASTAR_ERROR_NULL_POINTER,
+    };
+
+    let grid_nodes_amount = handle.grid.get_nodes_amount();
+
+    if nodes_amount < grid_nodes_amount {
+        return ASTAR_ERROR_BUFFER_TOO_SMALL;
+    }
+
+    let goals: Vec<usize> = goals.iter()
+        .map(|goal| *goal as usize)
+        .collect();
+
+    let field = match FlowField::new(&handle.grid, &goals) {
+        Ok(field) => field,
+        Err(error) => return get_error_code(error),
+    };
+
+    distances[..grid_nodes_amount].copy_from_slice(field.get_distances());
+    directions[..grid_nodes_amount].copy_from_slice(field.get_directions());
+
+    0
+}
+
+/// Releases a grid created by `astar_grid_new`.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid handle, nothing is done if it is null
+///
+/// # Safety
+///
+/// `grid` must be null or returned by `astar_grid_new` and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn astar_grid_free(grid: *mut GridHandle) {
+
+    if !grid.is_null() {
+        drop(Box::from_raw(grid));
+    }
+}
+
+/// Previous interface that calculates and stores the path
+/// into the given C array, kept for the existing callers.
+///
+/// # Arguments:
+///
+/// The same arguments as `astar_find_path`.
+///
+/// # Returns:
+///
+/// `PATH_FOUND` if the path has been written, `PATH_BUFFER_TOO_SMALL`
+/// if it does not fit into the path array, otherwise the status code
+/// of the error: 1 if the arrival is unreachable, 2 if the departure
+/// is blocked, 3 if the arrival is blocked, 4 if an index is outside
+/// of the grid or if the grid is too large, 5 if the departure
+/// is the arrival (empty path), 7 if a null pointer is given
+/// for a non empty array, 10 if the cost of the path overflows.
+///
+/// # Safety
+///
+/// The same requirements as `astar_find_path`.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn get_path(
+    path: *mut u32,
+    path_capacity: size_t,
+    walls: *const u32,
+    walls_amount: size_t,
+    weights: *const u8,
+    width: u32,
+    height: u32,
+    departure: u32,
+    arrival: u32,
+) -> c_int {
+
+    let costs = MoveCosts::default();
+
+    get_path_with_costs(
+        path,
+        path_capacity,
+        walls,
+        walls_amount,
+        weights,
+        width,
+        height,
+        departure,
+        arrival,
+        costs.get_horizontal_or_vertical(),
+        costs.get_diagonal(),
+    )
+}
+
+/// Previous interface that calculates and stores the path
+/// into the given C array, using the given movement costs.
+///
+/// # Arguments:
+///
+/// The same arguments as `astar_find_path_with_costs`.
+///
+/// # Returns:
+///
+/// The same status codes as `get_path`.
+///
+/// # Safety
+///
+/// The same requirements as `astar_find_path`.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn get_path_with_costs(
+    path: *mut u32,
+    path_capacity: size_t,
+    walls: *const u32,
+    walls_amount: size_t,
+    weights: *const u8,
+    width: u32,
+    height: u32,
+    departure: u32,
+    arrival: u32,
+    horizontal_or_vertical_cost: u32,
+    diagonal_cost: u32,
+) -> c_int {
+
+    let result = astar_find_path_with_costs(
+        path,
+        path_capacity,
+        walls,
+        walls_amount,
+        weights,
+        width,
+        height,
+        departure,
+        arrival,
+        horizontal_or_vertical_cost,
+        diagonal_cost,
+    );
+
+    if result >= 0 {
+        PATH_FOUND
+    } else {
+        -result as c_int
+    }
+}
@@ -0,0 +1,232 @@
+//! Distances to the nearest goal and next step of every node,
+//! so many units can move towards the same goals without researching
+//! one path each.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use grid::Grid;
+use path_error::PathError;
+
+/// Distance of the nodes that cannot reach any goal.
+pub const UNREACHABLE: u32 = u32::MAX;
+
+/// Direction code of the goals and of the nodes that cannot reach any goal.
+pub const NO_DIRECTION: i8 = -1;
+
+/// Horizontal and vertical offsets of the direction codes 0 to 7:
+/// right, bottom right, bottom, bottom left, left, top left, top
+/// and top right (the vertical axis goes down).
+pub const DIRECTION_OFFSETS: [(isize, isize); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+/// Cost of the cheapest path from every node to the nearest goal,
+/// and direction of the first movement of this path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlowField {
+    width: usize,
+    distances: Vec<u32>,
+    directions: Vec<i8>,
+}
+
+impl FlowField {
+
+    /// Floods the grid from the goals with Dijkstra's algorithm,
+    /// following the movements backward. The movement costs, the terrain
+    /// weights and the movement model of the grid are used.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid to flood
+    /// * `goals` - the indices of the goals, the unusuable ones are ignored
+    ///
+    /// # Returns:
+    ///
+    /// The flow field, or an error if a goal is outside of the grid.
+    pub fn new(
+        grid: &Grid,
+        goals: &[usize],
+    ) -> Result<FlowField, PathError> {
+
+        let nodes_amount = grid.get_nodes_amount();
+
+        let mut field = FlowField {
+            width: grid.get_width(),
+            distances: vec![UNREACHABLE; nodes_amount],
+            directions: vec![NO_DIRECTION; nodes_amount],
+        };
+
+        let mut open_list = BinaryHeap::new();
+
+        for goal in goals {
+
+            if *goal >= nodes_amount {
+                return Err(PathError::OutOfBounds);
+            }
+
+            if !grid.get_node(*goal).is_usuable() {
+                continue;
+            }
+
+            field.distances[*goal] = 0;
+            open_list.push(Reverse((0, *goal)));
+        }
+
+        let mut children = Vec::new();
+
+        while let Some(Reverse((distance, index))) = open_list.pop() {
+
+            /* outdated entry of a node whose distance has been lowered */
+            if distance > field.distances[index] {
+                continue;
+            }
+
+            grid.generate_children_list(index, &mut children);
+
+            for child in children.iter() {
+
+                if !grid.get_node(*child).is_usuable() {
+                    continue;
+                }
+
+                /* the child moves to the current node,
+                   so the weight of the current node is used */
+                let child_distance = distance.saturating_add(
+                    grid.get_movement_cost(*child, index),
+                );
+
+                if child_distance >= field.distances[*child] {
+                    continue;
+                }
+
+                field.distances[*child] = child_distance;
+                field.directions[*child] = field.get_direction_code(
+                    *child,
+                    index,
+                );
+
+                open_list.push(Reverse((child_distance, *child)));
+            }
+        }
+
+        Ok(field)
+    }
+
+    /// Returns the direction code of the movement between two neighbours.
+    ///
+    /// # Arguments:
+    ///
+    /// * `from` - the index of the node
+    /// * `to` - the index of the neighbour
+    ///
+    /// # Returns:
+    ///
+    /// The direction code, between 0 and 7.
+    fn get_direction_code(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> i8 {
+
+        let horizontal_offset = (to % self.width) as isize -
+            (from % self.width) as isize;
+        let vertical_offset = (to / self.width) as isize -
+            (from / self.width) as isize;
+
+        DIRECTION_OFFSETS.iter()
+            .position(|offset| *offset == (horizontal_offset, vertical_offset))
+            .expect("the nodes are neighbours") as i8
+    }
+
+    /// Returns the cost of the cheapest path from one node to the nearest goal.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The distance, `UNREACHABLE` if no goal can be reached.
+    pub fn get_distance(
+        &self,
+        index: usize,
+    ) -> u32 {
+        self.distances[index]
+    }
+
+    /// Returns the direction of the first movement from one node
+    /// to the nearest goal.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The direction code (see `DIRECTION_OFFSETS`),
+    /// `NO_DIRECTION` for a goal or if no goal can be reached.
+    pub fn get_direction(
+        &self,
+        index: usize,
+    ) -> i8 {
+        self.directions[index]
+    }
+
+    /// Returns the next node on the way from one node to the nearest goal.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The index of the next node, None for a goal
+    /// or if no goal can be reached.
+    pub fn get_next_index(
+        &self,
+        index: usize,
+    ) -> Option<usize> {
+
+        let direction = self.directions[index];
+
+        if direction == NO_DIRECTION {
+            return None;
+        }
+
+        let (
+            horizontal_offset,
+            vertical_offset,
+        ) = DIRECTION_OFFSETS[direction as usize];
+
+        Some(
+            (index as isize + vertical_offset * self.width as isize +
+                horizontal_offset) as usize
+        )
+    }
+
+    /// Getter of the distances.
+    ///
+    /// # Returns:
+    ///
+    /// The distance of every node, ordered by index.
+    pub fn get_distances(&self) -> &[u32] {
+        &self.distances
+    }
+
+    /// Getter of the directions.
+    ///
+    /// # Returns:
+    ///
+    /// The direction code of every node, ordered by index.
+    pub fn get_directions(&self) -> &[i8] {
+        &self.directions
+    }
+}
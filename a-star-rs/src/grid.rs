@@ -0,0 +1,797 @@
+//! Map data shared by the researches: the nodes and the movement rules.
+
+use std::sync::atomic::{
+    AtomicU64,
+    Ordering,
+};
+use std::sync::{
+    Arc,
+    OnceLock,
+};
+
+use heuristic::Heuristic;
+use line_of_sight::walk_supercover_line;
+use move_costs::MoveCosts;
+use movement::Movement;
+use node::Node;
+use path_error::PathError;
+use point::Point;
+
+/// Last revision given to a state of a grid. The revisions are shared
+/// by all the grids, so two states never have the same revision.
+static LAST_REVISION: AtomicU64 = AtomicU64::new(0);
+
+/// Grid of nodes with its movement model, movement costs and heuristic.
+///
+/// A research never modifies the grid, so one grid can be shared
+/// between threads that run their own researches,
+/// each one with its own `SearchContext`.
+#[derive(Clone)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    nodes: Vec<Node>,
+    heuristic: Option<Arc<dyn Heuristic>>,
+    movement: Movement,
+    costs: MoveCosts,
+    revision: u64,
+    jump_point_support: OnceLock<bool>,
+}
+
+impl Grid {
+
+    /// Constructor for a new Grid object, every node is usuable.
+    ///
+    /// # Arguments:
+    ///
+    /// * `width` - the number of nodes per line
+    /// * `height` - the number of lines
+    ///
+    /// # Returns:
+    ///
+    /// Created Grid object.
+    ///
+    /// # Panics:
+    ///
+    /// If the amount of nodes does not fit into a `usize`.
+    pub fn new(
+        width: usize,
+        height: usize,
+    ) -> Grid {
+
+        let nodes_amount = width.checked_mul(height)
+            .expect("the amount of nodes of the grid overflows");
+
+        Grid {
+            width,
+            height,
+            nodes: vec![Node::new(); nodes_amount],
+            heuristic: None,
+            movement: Movement::default(),
+            costs: MoveCosts::default(),
+            revision: get_new_revision(),
+            jump_point_support: OnceLock::new(),
+        }
+    }
+
+    /// Getter of the revision.
+    ///
+    /// # Returns:
+    ///
+    /// The revision of the current state of the grid: it changes
+    /// every time a node, the movement model or the movement costs
+    /// may have been modified, and is never shared with another grid.
+    pub fn get_revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Returns if the jump point research supports the grid,
+    /// checked only once per revision.
+    ///
+    /// # Arguments:
+    ///
+    /// * `check` - checks the current state of the grid
+    ///
+    /// # Returns:
+    ///
+    /// The result of the check of the current revision.
+    pub(crate) fn get_jump_point_support<F>(
+        &self,
+        check: F,
+    ) -> bool
+    where
+        F: FnOnce(&Grid) -> bool,
+    {
+        *self.jump_point_support.get_or_init(|| check(self))
+    }
+
+    /// Gives a new revision to the grid, after a modification.
+    fn update_revision(&mut self) {
+        self.revision = get_new_revision();
+        self.jump_point_support = OnceLock::new();
+    }
+
+    /// Getter of the width.
+    ///
+    /// # Returns:
+    ///
+    /// The number of nodes per line.
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Getter of the height.
+    ///
+    /// # Returns:
+    ///
+    /// The number of lines.
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    /// Getter of the amount of nodes into the grid.
+    ///
+    /// # Returns:
+    ///
+    /// The width multiplied by the height.
+    pub fn get_nodes_amount(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns a node for read access.
+    ///
+    /// # Arguments:
+    ///
+    /// `index` - the index of the node to get
+    ///
+    /// # Returns:
+    ///
+    /// The node to read.
+    pub fn get_node(
+        &self,
+        index: usize,
+    ) -> &Node {
+        &self.nodes[index]
+    }
+
+    /// Returns a node for read and write access.
+    ///
+    /// # Arguments:
+    ///
+    /// `index` - the index of the node to get
+    ///
+    /// # Returns:
+    ///
+    /// The node to read or write.
+    pub fn get_node_by_index(
+        &mut self,
+        index: usize,
+    ) -> &mut Node {
+        self.update_revision();
+        &mut self.nodes[index]
+    }
+
+    /// Returns a node from its coordinates for read and write access.
+    ///
+    /// # Arguments:
+    ///
+    /// * `x` - the column of the node
+    /// * `y` - the line of the node
+    ///
+    /// # Returns:
+    ///
+    /// The node to read or write, or an error if it is outside of the grid.
+    pub fn node_at(
+        &mut self,
+        x: usize,
+        y: usize,
+    ) -> Result<&mut Node, PathError> {
+        let index = self.get_index(Point::new(x, y))?;
+        self.update_revision();
+        Ok(&mut self.nodes[index])
+    }
+
+    /// Converts coordinates into an index.
+    ///
+    /// # Arguments:
+    ///
+    /// * `point` - the coordinates of the node
+    ///
+    /// # Returns:
+    ///
+    /// The index of the node, or an error if it is outside of the grid.
+    pub fn get_index(
+        &self,
+        point: Point,
+    ) -> Result<usize, PathError> {
+
+        if point.x >= self.width || point.y >= self.height {
+            return Err(PathError::OutOfBounds);
+        }
+
+        Ok(point.y * self.width + point.x)
+    }
+
+    /// Converts an index into coordinates.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The coordinates of the node, or an error if it is outside of the grid.
+    pub fn get_point(
+        &self,
+        index: usize,
+    ) -> Result<Point, PathError> {
+
+        if index >= self.nodes.len() {
+            return Err(PathError::OutOfBounds);
+        }
+
+        let (x, y) = self.get_positions(index);
+
+        Ok(Point::new(x, y))
+    }
+
+    /// Sets the heuristic used to estimate the cost to the arrival node.
+    /// By default, the heuristic depends on the movement model.
+    ///
+    /// # Arguments:
+    ///
+    /// * `heuristic` - the heuristic, or a closure taking the horizontal
+    ///   and vertical distances to the arrival node and the movement costs
+    pub fn set_heuristic<H: Heuristic + 'static>(
+        &mut self,
+        heuristic: H,
+    ) {
+        self.heuristic = Some(Arc::new(heuristic));
+    }
+
+    /// Sets the terrain weight of one node.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    /// * `weight` - the terrain weight, the movement cost to the node
+    ///   is multiplied by this value; 0 sets the node as unusuable
+    ///
+    /// # Returns:
+    ///
+    /// An error if the index is outside of the grid.
+    pub fn set_weight(
+        &mut self,
+        index: usize,
+        weight: u8,
+    ) -> Result<(), PathError> {
+
+        self.update_revision();
+
+        match self.nodes.get_mut(index) {
+            Some(node) => {
+                node.set_weight(weight);
+                Ok(())
+            },
+            None => Err(PathError::OutOfBounds),
+        }
+    }
+
+    /// Sets one node as unusuable or usuable.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    /// * `blocked` - true if the node cannot be part of a path
+    ///
+    /// # Returns:
+    ///
+    /// An error if the index is outside of the grid.
+    pub fn set_blocked(
+        &mut self,
+        index: usize,
+        blocked: bool,
+    ) -> Result<(), PathError> {
+
+        self.update_revision();
+
+        match self.nodes.get_mut(index) {
+            Some(node) => {
+                node.set_blocked(blocked);
+                Ok(())
+            },
+            None => Err(PathError::OutOfBounds),
+        }
+    }
+
+    /// Sets the terrain weights of all the nodes at once.
+    ///
+    /// # Arguments:
+    ///
+    /// * `weights` - one weight per node, ordered by index;
+    ///   a weight of 0 sets the node as unusuable,
+    ///   the other ones set the node as usuable
+    ///
+    /// # Returns:
+    ///
+    /// An error if there is not exactly one weight per node.
+    pub fn set_weights(
+        &mut self,
+        weights: &[u8],
+    ) -> Result<(), PathError> {
+
+        if weights.len() != self.nodes.len() {
+            return Err(PathError::OutOfBounds);
+        }
+
+        self.update_revision();
+
+        for (node, weight) in self.nodes.iter_mut().zip(weights.iter()) {
+
+            if *weight != 0 {
+                node.set_usuable();
+            }
+
+            node.set_weight(*weight);
+        }
+
+        Ok(())
+    }
+
+    /// Sets the base costs of the movements. The default heuristics
+    /// are scaled to these costs.
+    ///
+    /// # Arguments:
+    ///
+    /// * `costs` - the horizontal or vertical and diagonal movement costs
+    pub fn set_move_costs(
+        &mut self,
+        costs: MoveCosts,
+    ) {
+        self.costs = costs;
+        self.update_revision();
+    }
+
+    /// Getter of the movement costs.
+    ///
+    /// # Returns:
+    ///
+    /// The base costs of the movements.
+    pub fn get_move_costs(&self) -> MoveCosts {
+        self.costs
+    }
+
+    /// Sets the movement model that defines the children of every node.
+    /// All the eight directions are allowed by default.
+    ///
+    /// # Arguments:
+    ///
+    /// * `movement` - the movement model
+    pub fn set_movement(
+        &mut self,
+        movement: Movement,
+    ) {
+        self.movement = movement;
+        self.update_revision();
+    }
+
+    /// Getter of the movement model.
+    ///
+    /// # Returns:
+    ///
+    /// The movement model of the grid.
+    pub fn get_movement(&self) -> Movement {
+        self.movement
+    }
+
+    /// Estimates the cost from one node to the arrival node
+    /// with the heuristic of the grid.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    /// * `arrival` - the index of the arrival node
+    ///
+    /// # Returns:
+    ///
+    /// The estimated cost.
+    pub fn estimate_heuristic(
+        &self,
+        index: usize,
+        arrival: usize,
+    ) -> u32 {
+
+        let (
+            index_x,
+            index_y,
+        ) = self.get_positions(arrival);
+
+        let (
+            node_x,
+            node_y,
+        ) = self.get_positions(index);
+
+        let heuristic = match self.heuristic {
+            Some(ref heuristic) => &**heuristic,
+            None => self.movement.get_default_heuristic(),
+        };
+
+        heuristic.estimate(
+            node_x.abs_diff(index_x),
+            node_y.abs_diff(index_y),
+            &self.costs,
+        )
+    }
+
+    /// Generates the list of children of one node.
+    ///
+    /// Diagonal children are added according to the movement model.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    /// * `children` - the list the children are written into,
+    ///   its previous content is removed
+    pub fn generate_children_list(
+        &self,
+        index: usize,
+        children: &mut Vec<usize>,
+    ) {
+
+        children.clear();
+
+        let (
+            horizontal_position,
+            vertical_position,
+        ) = self.get_positions(index);
+
+        let has_left = horizontal_position != 0;
+        let has_right = horizontal_position != self.width - 1;
+        let has_top = vertical_position != 0;
+        let has_bottom = vertical_position != self.height - 1;
+
+        if has_left {
+            children.push(index - 1);
+        }
+
+        if has_right {
+            children.push(index + 1);
+        }
+
+        if has_top {
+            let top = index - self.width;
+            children.push(top);
+
+            if has_right && self.is_diagonal_allowed(index, top, top + 1) {
+                children.push(top + 1);
+            }
+
+            if has_left && self.is_diagonal_allowed(index, top, top - 1) {
+                children.push(top - 1);
+            }
+        }
+
+        if has_bottom {
+            let bottom = index + self.width;
+            children.push(bottom);
+
+            if
+                has_right &&
+                self.is_diagonal_allowed(index, bottom, bottom + 1)
+            {
+                children.push(bottom + 1);
+            }
+
+            if
+                has_left &&
+                self.is_diagonal_allowed(index, bottom, bottom - 1)
+            {
+                children.push(bottom - 1);
+            }
+        }
+    }
+
+    /// Indicates if the diagonal movement from one node
+    /// to the given diagonal child is allowed by the movement model.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    /// * `vertical_index` - the vertical neighbour of the node
+    /// * `diagonal_index` - the diagonal child, next to the vertical neighbour
+    ///
+    /// # Returns:
+    ///
+    /// True if the diagonal child can be reached.
+    fn is_diagonal_allowed(
+        &self,
+        index: usize,
+        vertical_index: usize,
+        diagonal_index: usize,
+    ) -> bool {
+
+        /* the horizontal neighbour is on the line of the node,
+           on the same side as the diagonal child */
+        let horizontal_index = index + diagonal_index - vertical_index;
+
+        self.movement.is_diagonal_allowed(
+            self.nodes[horizontal_index].is_usuable(),
+            self.nodes[vertical_index].is_usuable(),
+        )
+    }
+
+    /// Returns the cost of the movement from one node to one of its children.
+    ///
+    /// # Arguments:
+    ///
+    /// * `from` - the index of the node
+    /// * `to` - the index of the child
+    ///
+    /// # Returns:
+    ///
+    /// The base movement cost multiplied by the terrain weight of the child,
+    /// `u32::MAX` if the product does not fit into a cost.
+    pub fn get_movement_cost(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> u32 {
+
+        let mut cost = self.costs.get_diagonal();
+
+        /* compare the coordinates rather than the indices,
+           the last node of a line is followed by the first one
+           of the next line */
+        if
+            to % self.width == from % self.width ||
+            to / self.width == from / self.width
+        {
+            cost = self.costs.get_horizontal_or_vertical();
+        }
+
+        cost.saturating_mul(self.nodes[to].get_weight() as u32)
+    }
+
+    /// Indicates if the segment between the centres of two nodes only goes
+    /// through usuable nodes, the two nodes included. When the segment goes
+    /// exactly through the corner of two nodes, it follows the rules
+    /// of the diagonal movements, but never goes between two nodes
+    /// if one of them is unusuable without diagonal movements.
+    ///
+    /// # Arguments:
+    ///
+    /// * `from` - the index of the first node
+    /// * `to` - the index of the second node
+    ///
+    /// # Returns:
+    ///
+    /// True if the second node can be seen from the first one,
+    /// or an error if an index is outside of the grid.
+    pub fn has_line_of_sight(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<bool, PathError> {
+
+        let from = self.get_point(from)?;
+        let to = self.get_point(to)?;
+
+        Ok(walk_supercover_line(
+            from,
+            to,
+            |point, corner| {
+
+                if let Some((horizontal, vertical)) = corner {
+                    if !self.is_corner_crossable(
+                        point_index(self.width, horizontal),
+                        point_index(self.width, vertical),
+                    ) {
+                        return false;
+                    }
+                }
+
+                self.nodes[point_index(self.width, point)].is_usuable()
+            },
+        ))
+    }
+
+    /// Follows a ray from the centre of one node until it reaches
+    /// an unusuable node, with the same corner rules
+    /// as `has_line_of_sight`.
+    ///
+    /// # Arguments:
+    ///
+    /// * `from` - the index of the node the ray starts from
+    /// * `direction_x` - the horizontal component of the direction,
+    ///   to the right if positive
+    /// * `direction_y` - the vertical component of the direction,
+    ///   to the bottom if positive
+    /// * `max_distance` - the length of the ray, the distance between
+    ///   two neighbour nodes being 1
+    ///
+    /// # Returns:
+    ///
+    /// The first unusuable node reached by the ray (the first node itself
+    /// if unusuable), None if the ray ends or leaves the grid before,
+    /// or an error if the index is outside of the grid.
+    pub fn raycast(
+        &self,
+        from: usize,
+        direction_x: f64,
+        direction_y: f64,
+        max_distance: f64,
+    ) -> Result<Option<usize>, PathError> {
+
+        /* two borders crossed at distances closer than that
+           are crossed at once, through their corner */
+        const CORNER_TOLERANCE: f64 = 1e-9;
+
+        let point = self.get_point(from)?;
+
+        if !self.nodes[from].is_usuable() {
+            return Ok(Some(from));
+        }
+
+        let norm = direction_x.hypot(direction_y);
+
+        if norm == 0.0 || !norm.is_finite() {
+            return Ok(None);
+        }
+
+        let (mut x, mut y) = (point.x as i64, point.y as i64);
+        let (horizontal_sign, vertical_sign) = (
+            if direction_x < 0.0 { -1 } else { 1 },
+            if direction_y < 0.0 { -1 } else { 1 },
+        );
+
+        /* distance to the next border and between two borders,
+           for each axis, the ray starting at the centre of the node */
+        let get_distances = |component: f64| {
+            if component == 0.0 {
+                (f64::INFINITY, f64::INFINITY)
+            } else {
+                let delta = norm / component.abs();
+                (delta / 2.0, delta)
+            }
+        };
+
+        let (mut horizontal_distance, horizontal_delta) =
+            get_distances(direction_x);
+        let (mut vertical_distance, vertical_delta) =
+            get_distances(direction_y);
+
+        let index_at = |x: i64, y: i64| {
+            if
+                x < 0 ||
+                y < 0 ||
+                x >= self.width as i64 ||
+                y >= self.height as i64
+            {
+                None
+            } else {
+                Some(y as usize * self.width + x as usize)
+            }
+        };
+
+        loop {
+
+            let distance = horizontal_distance.min(vertical_distance);
+
+            if distance > max_distance {
+                return Ok(None);
+            }
+
+            if (horizontal_distance - vertical_distance).abs() < CORNER_TOLERANCE {
+
+                let horizontal = index_at(x + horizontal_sign, y);
+                let vertical = index_at(x, y + vertical_sign);
+
+                if let (Some(horizontal), Some(vertical)) = (horizontal, vertical) {
+                    if !self.is_corner_crossable(horizontal, vertical) {
+                        return Ok(Some(
+                            if self.nodes[horizontal].is_usuable() {
+                                vertical
+                            } else {
+                                horizontal
+                            }
+                        ));
+                    }
+                }
+
+                x += horizontal_sign;
+                y += vertical_sign;
+                horizontal_distance += horizontal_delta;
+                vertical_distance += vertical_delta;
+            } else if horizontal_distance < vertical_distance {
+                x += horizontal_sign;
+                horizontal_distance += horizontal_delta;
+            } else {
+                y += vertical_sign;
+                vertical_distance += vertical_delta;
+            }
+
+            match index_at(x, y) {
+                Some(index) if !self.nodes[index].is_usuable() =>
+                    return Ok(Some(index)),
+                Some(_) => {},
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Indicates if a segment can go through the corner between
+    /// two nodes: if the diagonal movement between them is allowed,
+    /// or if both of them are usuable.
+    ///
+    /// # Arguments:
+    ///
+    /// * `horizontal_index` - the node next to the corner on the side
+    ///   of the segment start, horizontally
+    /// * `vertical_index` - the node next to the corner on the side
+    ///   of the segment start, vertically
+    ///
+    /// # Returns:
+    ///
+    /// True if the corner can be crossed.
+    fn is_corner_crossable(
+        &self,
+        horizontal_index: usize,
+        vertical_index: usize,
+    ) -> bool {
+
+        let horizontal_usuable = self.nodes[horizontal_index].is_usuable();
+        let vertical_usuable = self.nodes[vertical_index].is_usuable();
+
+        (horizontal_usuable && vertical_usuable) ||
+            self.movement.is_diagonal_allowed(
+                horizontal_usuable,
+                vertical_usuable,
+            )
+    }
+
+    /// Returns the horizontal and vertical position for the given index.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the source index
+    ///
+    /// # Returns:
+    ///
+    /// Tuple that contains the horizontal and vertical positions.
+    fn get_positions(
+        &self,
+        index: usize,
+    ) -> (usize, usize) {
+
+        (
+            index % self.width,
+            index / self.width,
+        )
+    }
+}
+
+/// Converts coordinates inside of a grid into an index.
+///
+/// # Arguments:
+///
+/// * `width` - the number of nodes per line of the grid
+/// * `point` - the coordinates of the node
+///
+/// # Returns:
+///
+/// The index of the node.
+fn point_index(
+    width: usize,
+    point: Point,
+) -> usize {
+    point.y * width + point.x
+}
+
+/// Returns a revision that has never been given to a grid.
+///
+/// # Returns:
+///
+/// The new revision.
+fn get_new_revision() -> u64 {
+    LAST_REVISION.fetch_add(1, Ordering::Relaxed) + 1
+}
@@ -0,0 +1,157 @@
+//! Heuristics that estimate the cost from one node to the arrival node.
+
+use move_costs::MoveCosts;
+
+/// Estimation of the cost between two nodes, expressed in the same units
+/// as the movement costs. The search is guaranteed to return the shortest
+/// path only if the estimation never exceeds the real cost (admissible).
+///
+/// Any closure taking the horizontal and vertical distances (in nodes)
+/// and the movement costs, and returning the estimated cost,
+/// is also a heuristic.
+pub trait Heuristic: Send + Sync {
+
+    /// Estimates the cost of the path between two nodes.
+    ///
+    /// # Arguments:
+    ///
+    /// * `horizontal_distance` - the amount of columns between the nodes
+    /// * `vertical_distance` - the amount of lines between the nodes
+    /// * `costs` - the movement costs the estimation is scaled to
+    ///
+    /// # Returns:
+    ///
+    /// The estimated cost.
+    fn estimate(
+        &self,
+        horizontal_distance: usize,
+        vertical_distance: usize,
+        costs: &MoveCosts,
+    ) -> u32;
+}
+
+/// Sum of the horizontal and vertical distances,
+/// for grids without diagonal movements.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Manhattan;
+
+impl Heuristic for Manhattan {
+
+    fn estimate(
+        &self,
+        horizontal_distance: usize,
+        vertical_distance: usize,
+        costs: &MoveCosts,
+    ) -> u32 {
+        ((horizontal_distance + vertical_distance) as u32)
+            .saturating_mul(costs.get_horizontal_or_vertical())
+    }
+}
+
+/// Greatest of the horizontal and vertical distances,
+/// as if diagonal movements cost as much as the other ones.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Chebyshev;
+
+impl Heuristic for Chebyshev {
+
+    fn estimate(
+        &self,
+        horizontal_distance: usize,
+        vertical_distance: usize,
+        costs: &MoveCosts,
+    ) -> u32 {
+        (horizontal_distance.max(vertical_distance) as u32).saturating_mul(
+            costs.get_horizontal_or_vertical().min(costs.get_diagonal()),
+        )
+    }
+}
+
+/// Exact cost of the path without any unusuable node
+/// when diagonal movements are allowed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Octile;
+
+impl Heuristic for Octile {
+
+    fn estimate(
+        &self,
+        horizontal_distance: usize,
+        vertical_distance: usize,
+        costs: &MoveCosts,
+    ) -> u32 {
+
+        let diagonal_moves = horizontal_distance.min(vertical_distance);
+        let straight_moves = horizontal_distance.max(vertical_distance) -
+            diagonal_moves;
+
+        /* two straight moves are used if cheaper than one diagonal move,
+           and diagonal moves if cheaper than the straight ones */
+        let straight_move = costs.get_horizontal_or_vertical()
+            .min(costs.get_diagonal());
+        let diagonal_move = costs.get_diagonal()
+            .min(straight_move.saturating_mul(2));
+
+        (diagonal_moves as u32)
+            .saturating_mul(diagonal_move)
+            .saturating_add((straight_moves as u32).saturating_mul(straight_move))
+    }
+}
+
+/// Straight line distance between the two nodes.
+///
+/// The distance is scaled down if the diagonal movement is cheaper
+/// than its real length, so the estimation never exceeds the real cost.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Euclidean;
+
+impl Heuristic for Euclidean {
+
+    fn estimate(
+        &self,
+        horizontal_distance: usize,
+        vertical_distance: usize,
+        costs: &MoveCosts,
+    ) -> u32 {
+
+        let unit = (costs.get_horizontal_or_vertical() as f64)
+            .min(costs.get_diagonal() as f64 / 2f64.sqrt());
+
+        let distance = (
+            (horizontal_distance as f64).powi(2) +
+            (vertical_distance as f64).powi(2)
+        ).sqrt();
+
+        (distance * unit) as u32
+    }
+}
+
+/// No estimation at all, the search behaves like Dijkstra's algorithm.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Zero;
+
+impl Heuristic for Zero {
+
+    fn estimate(
+        &self,
+        _horizontal_distance: usize,
+        _vertical_distance: usize,
+        _costs: &MoveCosts,
+    ) -> u32 {
+        0
+    }
+}
+
+impl<F> Heuristic for F
+where
+    F: Fn(usize, usize, &MoveCosts) -> u32 + Send + Sync,
+{
+    fn estimate(
+        &self,
+        horizontal_distance: usize,
+        vertical_distance: usize,
+        costs: &MoveCosts,
+    ) -> u32 {
+        self(horizontal_distance, vertical_distance, costs)
+    }
+}
@@ -0,0 +1,938 @@
+//! Hierarchical research (HPA*): the grid is split into clusters,
+//! the research runs on the graph of their entrances first,
+//! then every part of the path is researched inside of one cluster.
+
+use std::cmp::Reverse;
+use std::collections::{
+    BinaryHeap,
+    HashMap,
+};
+
+use grid::Grid;
+use path_error::PathError;
+
+/// Minimal length of a border opening that gets two entrances,
+/// one at each end, instead of one in its middle.
+const SPLIT_ENTRANCE_LENGTH: usize = 6;
+
+/// Cost of the nodes that cannot be reached.
+const INFINITE: u32 = u32::MAX;
+
+/// Entrances of one cluster and the costs of the paths between them.
+#[derive(Clone, Debug, Default)]
+struct Cluster {
+
+    /// Indices of the entrance nodes, sorted in ascending order.
+    nodes: Vec<usize>,
+
+    /// For every entrance node, the nodes of the neighbour clusters
+    /// it can move to.
+    links: Vec<Vec<usize>>,
+
+    /// For every entrance node, the other entrance nodes it can reach
+    /// inside of the cluster and the cost of the cheapest path to them.
+    edges: Vec<Vec<(usize, u32)>>,
+}
+
+/// Columns and lines covered by a cluster, the ends are excluded.
+#[derive(Clone, Copy, Debug)]
+struct Area {
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+}
+
+impl Area {
+
+    /// Indicates if a node is inside of the area.
+    ///
+    /// # Arguments:
+    ///
+    /// * `width` - the number of nodes per line of the grid
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// True if the node is inside of the area.
+    fn contains(
+        &self,
+        width: usize,
+        index: usize,
+    ) -> bool {
+
+        let (x, y) = (index % width, index / width);
+
+        x >= self.left && x < self.right && y >= self.top && y < self.bottom
+    }
+
+    /// Converts the index of a node of the grid into its index
+    /// into the area.
+    ///
+    /// # Arguments:
+    ///
+    /// * `width` - the number of nodes per line of the grid
+    /// * `index` - the index of the node, inside of the area
+    ///
+    /// # Returns:
+    ///
+    /// The index of the node into the area.
+    fn get_local_index(
+        &self,
+        width: usize,
+        index: usize,
+    ) -> usize {
+        (index / width - self.top) * (self.right - self.left) +
+            index % width - self.left
+    }
+
+    /// Returns the amount of nodes into the area.
+    ///
+    /// # Returns:
+    ///
+    /// The width multiplied by the height of the area.
+    fn get_nodes_amount(&self) -> usize {
+        (self.right - self.left) * (self.bottom - self.top)
+    }
+}
+
+/// Abstract graph of a grid split into square clusters:
+/// the entrances between the clusters and the costs of the paths
+/// between the entrances of every cluster.
+///
+/// The researched paths are close to the cheapest ones, but not
+/// always the cheapest: they go through the entrances of the clusters.
+/// After a change of a node, `notify_cell_changed` only rebuilds
+/// the cluster of the node (and the entrances of its neighbours).
+#[derive(Clone, Debug)]
+pub struct HierarchicalGrid {
+    cluster_size: usize,
+    width: usize,
+    height: usize,
+    clusters_per_line: usize,
+    clusters_per_column: usize,
+    entrances: HashMap<(usize, usize), Vec<(usize, usize)>>,
+    clusters: Vec<Cluster>,
+}
+
+impl HierarchicalGrid {
+
+    /// Builds the abstract graph of a grid.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid to split
+    /// * `cluster_size` - the number of nodes per line and per column
+    ///   of a cluster, at least 1 (the clusters of the right
+    ///   and bottom sides may be smaller)
+    ///
+    /// # Returns:
+    ///
+    /// Created HierarchicalGrid object.
+    pub fn new(
+        grid: &Grid,
+        cluster_size: usize,
+    ) -> HierarchicalGrid {
+
+        let cluster_size = cluster_size.max(1);
+        let clusters_per_line = grid.get_width().div_ceil(cluster_size);
+        let clusters_per_column = grid.get_height().div_ceil(cluster_size);
+
+        let mut hierarchy = HierarchicalGrid {
+            cluster_size,
+            width: grid.get_width(),
+            height: grid.get_height(),
+            clusters_per_line,
+            clusters_per_column,
+            entrances: HashMap::new(),
+            clusters: vec![
+                Cluster::default();
+                clusters_per_line * clusters_per_column
+            ],
+        };
+
+        for cluster in 0..hierarchy.clusters.len() {
+            for neighbour in hierarchy.get_neighbour_clusters(cluster) {
+                if neighbour > cluster {
+                    hierarchy.generate_entrances(grid, cluster, neighbour);
+                }
+            }
+        }
+
+        for cluster in 0..hierarchy.clusters.len() {
+            hierarchy.generate_nodes(cluster);
+            hierarchy.generate_edges(grid, cluster);
+        }
+
+        hierarchy
+    }
+
+    /// Getter of the cluster size.
+    ///
+    /// # Returns:
+    ///
+    /// The number of nodes per line and per column of a cluster.
+    pub fn get_cluster_size(&self) -> usize {
+        self.cluster_size
+    }
+
+    /// Returns the amount of entrance nodes of all the clusters.
+    ///
+    /// # Returns:
+    ///
+    /// The amount of nodes of the abstract graph.
+    pub fn get_entrances_amount(&self) -> usize {
+        self.clusters
+            .iter()
+            .map(|cluster| cluster.nodes.len())
+            .sum()
+    }
+
+    /// Returns the cluster of one node.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The index of the cluster, ordered like the nodes of the grid.
+    pub fn get_cluster(
+        &self,
+        index: usize,
+    ) -> usize {
+        (index / self.width / self.cluster_size) * self.clusters_per_line +
+            index % self.width / self.cluster_size
+    }
+
+    /// Takes into account a change of one node of the grid:
+    /// blocked, unblocked or weighted. Must be called after every
+    /// change, before the next research.
+    ///
+    /// The entrances of the cluster of the node are generated again,
+    /// and the costs between the entrances of this cluster and of the
+    /// neighbour clusters whose entrances have changed.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the modified grid
+    /// * `index` - the index of the modified node
+    ///
+    /// # Returns:
+    ///
+    /// An error if the index is outside of the grid.
+    pub fn notify_cell_changed(
+        &mut self,
+        grid: &Grid,
+        index: usize,
+    ) -> Result<(), PathError> {
+
+        if index >= self.width * self.height || index >= grid.get_nodes_amount() {
+            return Err(PathError::OutOfBounds);
+        }
+
+        let cluster = self.get_cluster(index);
+        let neighbours = self.get_neighbour_clusters(cluster);
+
+        for neighbour in neighbours.iter() {
+            self.generate_entrances(
+                grid,
+                cluster.min(*neighbour),
+                cluster.max(*neighbour),
+            );
+
+            /* the diagonal movements between two neighbours
+               may go around the node */
+            for other in neighbours.iter() {
+                if
+                    other > neighbour &&
+                    self.get_neighbour_clusters(*neighbour).contains(other)
+                {
+                    self.generate_entrances(grid, *neighbour, *other);
+                }
+            }
+        }
+
+        self.generate_nodes(cluster);
+        self.generate_edges(grid, cluster);
+
+        for neighbour in neighbours {
+
+            let previous_nodes = self.clusters[neighbour].nodes.clone();
+
+            self.generate_nodes(neighbour);
+
+            if self.clusters[neighbour].nodes != previous_nodes {
+                self.generate_edges(grid, neighbour);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Researches a path on the abstract graph, then researches
+    /// every part of it inside of its cluster.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid the abstract graph has been built from
+    /// * `departure` - the departure cell index
+    /// * `arrival` - the arrival cell index
+    ///
+    /// # Returns:
+    ///
+    /// The indices of the path nodes, departure excluded,
+    /// or the reason why there is no path.
+    pub fn research_path(
+        &self,
+        grid: &Grid,
+        departure: usize,
+        arrival: usize,
+    ) -> Result<Vec<usize>, PathError> {
+
+        let nodes_amount = grid.get_nodes_amount();
+
+        if
+            departure >= nodes_amount ||
+            arrival >= nodes_amount ||
+            nodes_amount != self.width * self.height
+        {
+            return Err(PathError::OutOfBounds);
+        }
+
+        if !grid.get_node(departure).is_usuable() {
+            return Err(PathError::DepartureBlocked);
+        }
+
+        if !grid.get_node(arrival).is_usuable() {
+            return Err(PathError::ArrivalBlocked);
+        }
+
+        if departure == arrival {
+            return Err(PathError::DepartureIsArrival);
+        }
+
+        let abstract_path = self.research_abstract_path(
+            grid,
+            departure,
+            arrival,
+        ).ok_or(PathError::Unreachable)?;
+
+        let mut path = Vec::new();
+        let mut previous = departure;
+
+        for index in abstract_path {
+
+            let from_cluster = self.get_cluster(previous);
+
+            if from_cluster == self.get_cluster(index) {
+
+                let area = self.get_area(from_cluster);
+                let (_, parents) = research_in_area(
+                    grid,
+                    area,
+                    previous,
+                    &[index],
+                    false,
+                );
+
+                let start = path.len();
+                let mut current = index;
+
+                while current != previous {
+                    path.push(current);
+                    current = parents[area.get_local_index(self.width, current)]
+                        .expect("every reached node has a parent");
+                }
+
+                path[start..].reverse();
+            } else {
+                path.push(index);
+            }
+
+            previous = index;
+        }
+
+        Ok(path)
+    }
+
+    /// Researches the cheapest path on the abstract graph,
+    /// with the departure and the arrival connected to the entrances
+    /// of their clusters.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    /// * `departure` - the departure cell index
+    /// * `arrival` - the arrival cell index
+    ///
+    /// # Returns:
+    ///
+    /// The nodes of the abstract path, departure excluded,
+    /// or None if the arrival cannot be reached.
+    fn research_abstract_path(
+        &self,
+        grid: &Grid,
+        departure: usize,
+        arrival: usize,
+    ) -> Option<Vec<usize>> {
+
+        let departure_cluster = self.get_cluster(departure);
+        let departure_area = self.get_area(departure_cluster);
+        let arrival_cluster = self.get_cluster(arrival);
+        let arrival_area = self.get_area(arrival_cluster);
+
+        let mut departure_targets = self.clusters[departure_cluster]
+            .nodes
+            .clone();
+
+        if departure_cluster == arrival_cluster {
+            departure_targets.push(arrival);
+        }
+
+        let (departure_costs, _) = research_in_area(
+            grid,
+            departure_area,
+            departure,
+            &departure_targets,
+            false,
+        );
+
+        let (arrival_costs, _) = research_in_area(
+            grid,
+            arrival_area,
+            arrival,
+            &self.clusters[arrival_cluster].nodes,
+            true,
+        );
+
+        let mut departure_edges: Vec<(usize, u32)> = self.clusters
+            [departure_cluster]
+            .nodes
+            .iter()
+            .map(|node| (
+                *node,
+                departure_costs[departure_area.get_local_index(self.width, *node)],
+            ))
+            .filter(|edge| edge.1 != INFINITE)
+            .collect();
+
+        if departure_cluster == arrival_cluster {
+
+            let cost = departure_costs[
+                departure_area.get_local_index(self.width, arrival)
+            ];
+
+            if cost != INFINITE {
+                departure_edges.push((arrival, cost));
+            }
+        }
+
+        /* the nodes of the abstract graph are numbered cluster by cluster,
+           the departure and the arrival come last */
+        let mut offsets = Vec::with_capacity(self.clusters.len());
+        let mut nodes_amount = 0;
+
+        for cluster in self.clusters.iter() {
+            offsets.push(nodes_amount);
+            nodes_amount += cluster.nodes.len();
+        }
+
+        let get_id = |index: usize| {
+
+            if index == departure {
+                return nodes_amount;
+            }
+
+            if index == arrival {
+                return nodes_amount + 1;
+            }
+
+            let cluster = self.get_cluster(index);
+
+            offsets[cluster] + self.clusters[cluster]
+                .nodes
+                .binary_search(&index)
+                .expect("the abstract nodes are entrances")
+        };
+
+        /* cost, parent and closed state of every node */
+        let mut states = vec![(INFINITE, departure, false); nodes_amount + 2];
+        let mut open_list = BinaryHeap::new();
+
+        states[nodes_amount].0 = 0;
+        open_list.push(Reverse((0, 0, departure)));
+
+        let mut children: Vec<(usize, u32)> = Vec::new();
+
+        while let Some(Reverse((_, _, current))) = open_list.pop() {
+
+            let current_id = get_id(current);
+            let (cost, _, closed) = states[current_id];
+
+            if closed {
+                continue;
+            }
+
+            if current == arrival {
+                break;
+            }
+
+            states[current_id].2 = true;
+            children.clear();
+
+            /* the departure may be an entrance node too */
+            self.generate_abstract_children(grid, current, &mut children);
+
+            if current == departure {
+                children.extend(departure_edges.iter().cloned());
+            } else if self.get_cluster(current) == arrival_cluster {
+
+                let arrival_cost = arrival_costs[
+                    arrival_area.get_local_index(self.width, current)
+                ];
+
+                if arrival_cost != INFINITE {
+                    children.push((arrival, arrival_cost));
+                }
+            }
+
+            for &(child, movement_cost) in children.iter() {
+
+                let child_id = get_id(child);
+                let child_cost = cost.saturating_add(movement_cost);
+                let (previous_cost, _, closed) = states[child_id];
+
+                if closed || previous_cost <= child_cost {
+                    continue;
+                }
+
+                /* the nodes closer to the arrival first
+                   when the estimated costs are equal */
+                let heuristic = grid.estimate_heuristic(child, arrival);
+
+                states[child_id] = (child_cost, current, false);
+                open_list.push(Reverse((
+                    child_cost.saturating_add(heuristic),
+                    heuristic,
+                    child,
+                )));
+            }
+        }
+
+        if states[nodes_amount + 1].0 == INFINITE {
+            return None;
+        }
+
+        let mut path = Vec::new();
+        let mut current = arrival;
+
+        while current != departure {
+            path.push(current);
+            current = states[get_id(current)].1;
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Generates the children of one entrance node on the abstract graph:
+    /// the other entrances of its cluster and the linked entrances
+    /// of the neighbour clusters.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    /// * `index` - the index of the entrance node
+    /// * `children` - the list the children and their costs are added to
+    fn generate_abstract_children(
+        &self,
+        grid: &Grid,
+        index: usize,
+        children: &mut Vec<(usize, u32)>,
+    ) {
+
+        let cluster = &self.clusters[self.get_cluster(index)];
+
+        let position = match cluster.nodes.binary_search(&index) {
+            Ok(position) => position,
+            Err(_) => return,
+        };
+
+        children.extend(cluster.edges[position].iter().cloned());
+        children.extend(
+            cluster.links[position]
+                .iter()
+                .map(|link| (*link, grid.get_movement_cost(index, *link))),
+        );
+    }
+
+    /// Returns the area covered by one cluster.
+    ///
+    /// # Arguments:
+    ///
+    /// * `cluster` - the index of the cluster
+    ///
+    /// # Returns:
+    ///
+    /// The columns and lines of the cluster.
+    fn get_area(
+        &self,
+        cluster: usize,
+    ) -> Area {
+
+        let left = cluster % self.clusters_per_line * self.cluster_size;
+        let top = cluster / self.clusters_per_line * self.cluster_size;
+
+        Area {
+            left,
+            top,
+            right: (left + self.cluster_size).min(self.width),
+            bottom: (top + self.cluster_size).min(self.height),
+        }
+    }
+
+    /// Returns the clusters next to one cluster, diagonals included.
+    ///
+    /// # Arguments:
+    ///
+    /// * `cluster` - the index of the cluster
+    ///
+    /// # Returns:
+    ///
+    /// The indices of the neighbour clusters.
+    fn get_neighbour_clusters(
+        &self,
+        cluster: usize,
+    ) -> Vec<usize> {
+
+        let x = cluster % self.clusters_per_line;
+        let y = cluster / self.clusters_per_line;
+
+        let mut neighbours = Vec::new();
+
+        for line in y.saturating_sub(1)..(y + 2).min(self.clusters_per_column) {
+            for column in x.saturating_sub(1)..(x + 2).min(self.clusters_per_line) {
+
+                if (column, line) != (x, y) {
+                    neighbours.push(line * self.clusters_per_line + column);
+                }
+            }
+        }
+
+        neighbours
+    }
+
+    /// Generates the entrances between two neighbour clusters.
+    ///
+    /// Every opening of a shared border gets one entrance in its middle,
+    /// or two entrances at its ends if it is long. The diagonal
+    /// movements between two unusuable nodes are entrances too,
+    /// as they cannot be replaced by horizontal and vertical movements.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the clusters
+    /// * `first` - the index of the first cluster
+    /// * `second` - the index of the second cluster, greater than the first
+    fn generate_entrances(
+        &mut self,
+        grid: &Grid,
+        first: usize,
+        second: usize,
+    ) {
+
+        let first_area = self.get_area(first);
+        let second_area = self.get_area(second);
+        let width = self.width;
+
+        let mut entrances = Vec::new();
+
+        /* the straight movements of the openings of the shared border */
+        let crossings: Vec<(usize, usize)> = if
+            first_area.top == second_area.top
+        {
+            (first_area.top..first_area.bottom)
+                .map(|line| (
+                    line * width + first_area.right - 1,
+                    line * width + second_area.left,
+                ))
+                .collect()
+        } else if first_area.left == second_area.left {
+            (first_area.left..first_area.right)
+                .map(|column| (
+                    (first_area.bottom - 1) * width + column,
+                    second_area.top * width + column,
+                ))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut opening: Vec<(usize, usize)> = Vec::new();
+
+        for crossing in crossings.iter().map(Some).chain(Some(None)) {
+
+            let usuable = match crossing {
+                Some(&(from, to)) =>
+                    grid.get_node(from).is_usuable() &&
+                    grid.get_node(to).is_usuable(),
+                None => false,
+            };
+
+            if usuable {
+                opening.push(*crossing.expect("the crossing exists"));
+                continue;
+            }
+
+            if opening.len() >= SPLIT_ENTRANCE_LENGTH {
+                entrances.push(opening[0]);
+                entrances.push(opening[opening.len() - 1]);
+            } else if !opening.is_empty() {
+                entrances.push(opening[opening.len() / 2]);
+            }
+
+            opening.clear();
+        }
+
+        /* the diagonal movements between two unusuable nodes */
+        let mut children = Vec::new();
+
+        for line in first_area.top..first_area.bottom {
+            for column in first_area.left..first_area.right {
+
+                let index = line * width + column;
+
+                let on_border =
+                    line + 1 == first_area.bottom ||
+                    column + 1 == first_area.right ||
+                    column == first_area.left;
+
+                if !on_border || !grid.get_node(index).is_usuable() {
+                    continue;
+                }
+
+                grid.generate_children_list(index, &mut children);
+
+                for child in children.iter() {
+
+                    let is_diagonal =
+                        *child % width != column && *child / width != line;
+
+                    if
+                        !is_diagonal ||
+                        !second_area.contains(width, *child) ||
+                        !grid.get_node(*child).is_usuable()
+                    {
+                        continue;
+                    }
+
+                    let horizontal = line * width + *child % width;
+                    let vertical = *child / width * width + column;
+
+                    if
+                        !grid.get_node(horizontal).is_usuable() &&
+                        !grid.get_node(vertical).is_usuable()
+                    {
+                        entrances.push((index, *child));
+                    }
+                }
+            }
+        }
+
+        self.entrances.insert((first, second), entrances);
+    }
+
+    /// Generates the entrance nodes of one cluster and their links
+    /// from the entrances shared with the neighbour clusters.
+    ///
+    /// # Arguments:
+    ///
+    /// * `cluster` - the index of the cluster
+    fn generate_nodes(
+        &mut self,
+        cluster: usize,
+    ) {
+
+        let mut links: Vec<(usize, usize)> = Vec::new();
+
+        for neighbour in self.get_neighbour_clusters(cluster) {
+
+            let key = (cluster.min(neighbour), cluster.max(neighbour));
+
+            if let Some(entrances) = self.entrances.get(&key) {
+                for &(first, second) in entrances.iter() {
+                    if cluster == key.0 {
+                        links.push((first, second));
+                    } else {
+                        links.push((second, first));
+                    }
+                }
+            }
+        }
+
+        links.sort();
+
+        let mut nodes: Vec<usize> = links.iter()
+            .map(|link| link.0)
+            .collect();
+        nodes.dedup();
+
+        let mut node_links = vec![Vec::new(); nodes.len()];
+
+        for (node, link) in links {
+            let position = nodes.binary_search(&node)
+                .expect("every linked node is an entrance");
+            node_links[position].push(link);
+        }
+
+        let state = &mut self.clusters[cluster];
+        state.nodes = nodes;
+        state.links = node_links;
+    }
+
+    /// Generates the costs of the cheapest paths between the entrance
+    /// nodes of one cluster, inside of the cluster.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the cluster
+    /// * `cluster` - the index of the cluster
+    fn generate_edges(
+        &mut self,
+        grid: &Grid,
+        cluster: usize,
+    ) {
+
+        let area = self.get_area(cluster);
+        let width = self.width;
+        let nodes = self.clusters[cluster].nodes.clone();
+
+        let edges = nodes.iter()
+            .map(|node| {
+
+                let (costs, _) = research_in_area(
+                    grid,
+                    area,
+                    *node,
+                    &nodes,
+                    false,
+                );
+
+                nodes.iter()
+                    .filter(|other| *other != node)
+                    .map(|other| (
+                        *other,
+                        costs[area.get_local_index(width, *other)],
+                    ))
+                    .filter(|edge| edge.1 != INFINITE)
+                    .collect()
+            })
+            .collect();
+
+        self.clusters[cluster].edges = edges;
+    }
+}
+
+/// Researches the cheapest paths from one node to the nodes of an area
+/// without leaving it (Dijkstra's algorithm).
+///
+/// # Arguments:
+///
+/// * `grid` - the grid of the area
+/// * `area` - the columns and lines the paths stay into
+/// * `from` - the index of the first node, inside of the area
+/// * `targets` - the indices of the nodes whose costs are researched,
+///   the research stops once they are all reached
+/// * `reversed` - true to follow the movements backward: the costs
+///   are the costs of the paths from the nodes to the first node
+///
+/// # Returns:
+///
+/// The cost and the parent of every reached node of the area,
+/// ordered by index into the area.
+fn research_in_area(
+    grid: &Grid,
+    area: Area,
+    from: usize,
+    targets: &[usize],
+    reversed: bool,
+) -> (Vec<u32>, Vec<Option<usize>>) {
+
+    let width = grid.get_width();
+    let nodes_amount = area.get_nodes_amount();
+
+    let mut costs = vec![INFINITE; nodes_amount];
+    let mut parents = vec![None; nodes_amount];
+    let mut open_list = BinaryHeap::new();
+    let mut children = Vec::new();
+
+    if !grid.get_node(from).is_usuable() {
+        return (costs, parents);
+    }
+
+    let mut is_target = vec![false; nodes_amount];
+    let mut remaining_targets = 0;
+
+    for target in targets.iter() {
+
+        let local_index = area.get_local_index(width, *target);
+
+        if !is_target[local_index] {
+            is_target[local_index] = true;
+            remaining_targets += 1;
+        }
+    }
+
+    costs[area.get_local_index(width, from)] = 0;
+    open_list.push(Reverse((0, from)));
+
+    while let Some(Reverse((cost, index))) = open_list.pop() {
+
+        let local_index = area.get_local_index(width, index);
+
+        if cost > costs[local_index] {
+            continue;
+        }
+
+        if is_target[local_index] {
+
+            remaining_targets -= 1;
+
+            if remaining_targets == 0 {
+                break;
+            }
+        }
+
+        grid.generate_children_list(index, &mut children);
+
+        for child in children.iter() {
+
+            if
+                !area.contains(width, *child) ||
+                !grid.get_node(*child).is_usuable()
+            {
+                continue;
+            }
+
+            let child_cost = cost.saturating_add(if reversed {
+                grid.get_movement_cost(*child, index)
+            } else {
+                grid.get_movement_cost(index, *child)
+            });
+
+            let local_index = area.get_local_index(width, *child);
+
+            if child_cost >= costs[local_index] {
+                continue;
+            }
+
+            costs[local_index] = child_cost;
+            parents[local_index] = Some(index);
+            open_list.push(Reverse((child_cost, *child)));
+        }
+    }
+
+    (costs, parents)
+}
@@ -0,0 +1,773 @@
+//! Jump Point Search: the research jumps along the straight lines
+//! of uniform cost grids and only stops on the nodes where the cheapest
+//! paths can turn, the other nodes are never added to the open list.
+
+use flow_field::DIRECTION_OFFSETS;
+use grid::Grid;
+use move_costs::MoveCosts;
+use movement::Movement;
+use path_error::PathError;
+
+/// Indicates if the jump point research can run on a grid: the movement
+/// model must be `EightDirections` or `EightDirectionsNoCornerCutting`,
+/// every usuable node must have the same terrain weight
+/// and a diagonal movement must cost more than a horizontal or vertical one
+/// but less than two of them.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid to check
+///
+/// # Returns:
+///
+/// True if the jump point research returns the cheapest paths of the grid,
+/// the nodes being only checked once per revision of the grid.
+pub fn is_grid_supported(grid: &Grid) -> bool {
+    grid.get_jump_point_support(check_grid_support)
+}
+
+/// Checks the movement model, the movement costs and every node
+/// of the grid (see `is_grid_supported`).
+///
+/// # Arguments:
+///
+/// * `grid` - the grid to check
+///
+/// # Returns:
+///
+/// True if the jump point research returns the cheapest paths of the grid.
+fn check_grid_support(grid: &Grid) -> bool {
+
+    match grid.get_movement() {
+        Movement::EightDirections |
+        Movement::EightDirectionsNoCornerCutting => {},
+        _ => return false,
+    }
+
+    let costs = grid.get_move_costs();
+    let straight = costs.get_horizontal_or_vertical();
+    let diagonal = costs.get_diagonal();
+
+    if
+        straight == 0 ||
+        diagonal <= straight ||
+        diagonal as u64 >= 2 * straight as u64
+    {
+        return false;
+    }
+
+    let mut weights = (0..grid.get_nodes_amount())
+        .map(|index| grid.get_node(index))
+        .filter(|node| node.is_usuable())
+        .map(|node| node.get_weight());
+
+    match weights.next() {
+        Some(weight) => weights.all(|other| other == weight),
+        None => true,
+    }
+}
+
+/// Jump point found from one node in one direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Jump {
+
+    /// The index of the reached node.
+    pub index: usize,
+
+    /// The amount of movements from the node, all in the same direction.
+    pub steps: u32,
+}
+
+/// Movement rules of the jump point research on one grid.
+struct JumpGrid<'a> {
+    grid: &'a Grid,
+    width: isize,
+    height: isize,
+    corner_cutting: bool,
+}
+
+impl<'a> JumpGrid<'a> {
+
+    /// Constructor for a new JumpGrid object.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the supported grid to jump on
+    ///
+    /// # Returns:
+    ///
+    /// Created JumpGrid object.
+    fn new(grid: &'a Grid) -> JumpGrid<'a> {
+        JumpGrid {
+            grid,
+            width: grid.get_width() as isize,
+            height: grid.get_height() as isize,
+            corner_cutting: grid.get_movement() == Movement::EightDirections,
+        }
+    }
+
+    /// Indicates if a position is inside of the grid and usuable.
+    ///
+    /// # Arguments:
+    ///
+    /// * `x` - the column of the position
+    /// * `y` - the line of the position
+    ///
+    /// # Returns:
+    ///
+    /// True if a path can go through the position.
+    fn is_free(
+        &self,
+        x: isize,
+        y: isize,
+    ) -> bool {
+
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return false;
+        }
+
+        self.grid
+            .get_node((y * self.width + x) as usize)
+            .is_usuable()
+    }
+
+    /// Indicates if the movement from one position to its neighbour
+    /// in the given direction is allowed.
+    ///
+    /// # Arguments:
+    ///
+    /// * `x` - the column of the position
+    /// * `y` - the line of the position
+    /// * `dx` - the horizontal offset of the direction
+    /// * `dy` - the vertical offset of the direction
+    ///
+    /// # Returns:
+    ///
+    /// True if the neighbour can be reached.
+    fn can_move(
+        &self,
+        x: isize,
+        y: isize,
+        dx: isize,
+        dy: isize,
+    ) -> bool {
+
+        if !self.is_free(x + dx, y + dy) {
+            return false;
+        }
+
+        if dx == 0 || dy == 0 || self.corner_cutting {
+            return true;
+        }
+
+        self.is_free(x + dx, y) && self.is_free(x, y + dy)
+    }
+
+    /// Indicates if a position reached in the given direction has
+    /// a forced neighbour: a neighbour whose cheapest path goes
+    /// through the position, so the research has to stop there.
+    ///
+    /// # Arguments:
+    ///
+    /// * `x` - the column of the position
+    /// * `y` - the line of the position
+    /// * `dx` - the horizontal offset of the direction
+    /// * `dy` - the vertical offset of the direction
+    ///
+    /// # Returns:
+    ///
+    /// True if the position is a jump point.
+    fn has_forced_neighbour(
+        &self,
+        x: isize,
+        y: isize,
+        dx: isize,
+        dy: isize,
+    ) -> bool {
+
+        if self.corner_cutting {
+
+            if dx != 0 && dy != 0 {
+                return
+                    (self.is_free(x - dx, y + dy) && !self.is_free(x - dx, y)) ||
+                    (self.is_free(x + dx, y - dy) && !self.is_free(x, y - dy));
+            }
+
+            if dx != 0 {
+                return
+                    (self.is_free(x + dx, y + 1) && !self.is_free(x, y + 1)) ||
+                    (self.is_free(x + dx, y - 1) && !self.is_free(x, y - 1));
+            }
+
+            return
+                (self.is_free(x + 1, y + dy) && !self.is_free(x + 1, y)) ||
+                (self.is_free(x - 1, y + dy) && !self.is_free(x - 1, y));
+        }
+
+        /* without corner cutting, the diagonal movements
+           never have forced neighbours */
+        if dx != 0 && dy != 0 {
+            return false;
+        }
+
+        if dx != 0 {
+            return
+                (self.is_free(x, y + 1) && !self.is_free(x - dx, y + 1)) ||
+                (self.is_free(x, y - 1) && !self.is_free(x - dx, y - 1));
+        }
+
+        (self.is_free(x + 1, y) && !self.is_free(x + 1, y - dy)) ||
+            (self.is_free(x - 1, y) && !self.is_free(x - 1, y - dy))
+    }
+
+    /// Generates the directions the research follows from one node,
+    /// according to the direction the node has been reached from.
+    ///
+    /// # Arguments:
+    ///
+    /// * `x` - the column of the node
+    /// * `y` - the line of the node
+    /// * `direction` - the offsets of the movement that reached the node,
+    ///   None for the departure
+    ///
+    /// # Returns:
+    ///
+    /// The followed directions, one bit per direction code
+    /// (see `DIRECTION_OFFSETS`).
+    fn generate_directions(
+        &self,
+        x: isize,
+        y: isize,
+        direction: Option<(isize, isize)>,
+    ) -> u8 {
+
+        let (dx, dy) = match direction {
+            Some(direction) => direction,
+            None => return self.filter_directions(x, y, 0xFF),
+        };
+
+        let mut directions = get_direction_bit(dx, dy);
+
+        if dx != 0 && dy != 0 {
+
+            directions |= get_direction_bit(dx, 0) | get_direction_bit(0, dy);
+
+            if self.corner_cutting {
+
+                if !self.is_free(x - dx, y) {
+                    directions |= get_direction_bit(-dx, dy);
+                }
+
+                if !self.is_free(x, y - dy) {
+                    directions |= get_direction_bit(dx, -dy);
+                }
+            }
+        } else {
+
+            /* the perpendicular offsets of a horizontal
+               or vertical direction */
+            let (px, py) = (dy, dx);
+
+            for side in [1, -1].iter() {
+
+                let (sx, sy) = (px * side, py * side);
+
+                if self.corner_cutting {
+
+                    if !self.is_free(x + sx, y + sy) {
+                        directions |= get_direction_bit(dx + sx, dy + sy);
+                    }
+
+                    continue;
+                }
+
+                if
+                    self.is_free(x + sx, y + sy) &&
+                    !self.is_free(x - dx + sx, y - dy + sy)
+                {
+                    directions |= get_direction_bit(sx, sy) |
+                        get_direction_bit(dx + sx, dy + sy);
+                }
+            }
+        }
+
+        self.filter_directions(x, y, directions)
+    }
+
+    /// Removes the directions whose first movement is not allowed.
+    ///
+    /// # Arguments:
+    ///
+    /// * `x` - the column of the node
+    /// * `y` - the line of the node
+    /// * `directions` - one bit per direction code
+    ///
+    /// # Returns:
+    ///
+    /// The allowed directions, one bit per direction code.
+    fn filter_directions(
+        &self,
+        x: isize,
+        y: isize,
+        directions: u8,
+    ) -> u8 {
+
+        let mut allowed = 0;
+
+        for (code, &(dx, dy)) in DIRECTION_OFFSETS.iter().enumerate() {
+            if directions & (1 << code) != 0 && self.can_move(x, y, dx, dy) {
+                allowed |= 1 << code;
+            }
+        }
+
+        allowed
+    }
+
+    /// Moves from one position in one direction until a jump point,
+    /// the arrival or an unusuable node.
+    ///
+    /// # Arguments:
+    ///
+    /// * `x` - the column of the position
+    /// * `y` - the line of the position
+    /// * `dx` - the horizontal offset of the direction
+    /// * `dy` - the vertical offset of the direction
+    /// * `arrival` - the column and the line of the arrival
+    ///
+    /// # Returns:
+    ///
+    /// The amount of movements to the reached jump point or arrival,
+    /// or None if the direction is blocked first.
+    fn jump(
+        &self,
+        x: isize,
+        y: isize,
+        dx: isize,
+        dy: isize,
+        arrival: (isize, isize),
+    ) -> Option<u32> {
+
+        let (mut x, mut y) = (x, y);
+        let mut steps = 0;
+
+        loop {
+
+            if !self.can_move(x, y, dx, dy) {
+                return None;
+            }
+
+            x += dx;
+            y += dy;
+            steps += 1;
+
+            if (x, y) == arrival || self.has_forced_neighbour(x, y, dx, dy) {
+                return Some(steps);
+            }
+
+            if
+                dx != 0 &&
+                dy != 0 &&
+                (
+                    self.jump(x, y, dx, 0, arrival).is_some() ||
+                    self.jump(x, y, 0, dy, arrival).is_some()
+                )
+            {
+                return Some(steps);
+            }
+        }
+    }
+
+    /// Returns the column and the line of one node.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The column and the line.
+    fn get_position(
+        &self,
+        index: usize,
+    ) -> (isize, isize) {
+        (
+            index as isize % self.width,
+            index as isize / self.width,
+        )
+    }
+
+    /// Returns the index of the node reached by some movements
+    /// in one direction.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    /// * `dx` - the horizontal offset of the direction
+    /// * `dy` - the vertical offset of the direction
+    /// * `steps` - the amount of movements
+    ///
+    /// # Returns:
+    ///
+    /// The index of the reached node.
+    fn get_jump_index(
+        &self,
+        index: usize,
+        dx: isize,
+        dy: isize,
+        steps: u32,
+    ) -> usize {
+        (index as isize + (dy * self.width + dx) * steps as isize) as usize
+    }
+}
+
+/// Returns the bit of a direction into a set of directions.
+///
+/// # Arguments:
+///
+/// * `dx` - the horizontal offset of the direction
+/// * `dy` - the vertical offset of the direction
+///
+/// # Returns:
+///
+/// The bit of the direction code (see `DIRECTION_OFFSETS`).
+fn get_direction_bit(
+    dx: isize,
+    dy: isize,
+) -> u8 {
+
+    let code = match (dx, dy) {
+        (1, 0) => 0,
+        (1, 1) => 1,
+        (0, 1) => 2,
+        (-1, 1) => 3,
+        (-1, 0) => 4,
+        (-1, -1) => 5,
+        (0, -1) => 6,
+        _ => 7,
+    };
+
+    1 << code
+}
+
+/// Returns the offsets of the movement from one node to another one
+/// on the same line, column or diagonal.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid of the nodes
+/// * `from` - the index of the first node
+/// * `to` - the index of the second node
+///
+/// # Returns:
+///
+/// The horizontal and vertical offsets, between -1 and 1.
+fn get_direction(
+    grid: &Grid,
+    from: usize,
+    to: usize,
+) -> (isize, isize) {
+
+    let width = grid.get_width() as isize;
+
+    (
+        (to as isize % width - from as isize % width).signum(),
+        (to as isize / width - from as isize / width).signum(),
+    )
+}
+
+/// Generates the jump points reached from one node of the research.
+///
+/// # Arguments:
+///
+/// * `grid` - the supported grid of the research
+/// * `index` - the index of the node
+/// * `parent` - the index of the jump point the node has been reached from,
+///   None for the departure
+/// * `arrival` - the index of the arrival
+/// * `jumps` - the list the jump points are written into,
+///   its previous content is removed
+pub fn generate_jumps(
+    grid: &Grid,
+    index: usize,
+    parent: Option<usize>,
+    arrival: usize,
+    jumps: &mut Vec<Jump>,
+) {
+
+    let jump_grid = JumpGrid::new(grid);
+    let (x, y) = jump_grid.get_position(index);
+    let arrival_position = jump_grid.get_position(arrival);
+
+    let directions = jump_grid.generate_directions(
+        x,
+        y,
+        parent.map(|parent| get_direction(grid, parent, index)),
+    );
+
+    jumps.clear();
+
+    for (code, &(dx, dy)) in DIRECTION_OFFSETS.iter().enumerate() {
+
+        if directions & (1 << code) == 0 {
+            continue;
+        }
+
+        if let Some(steps) = jump_grid.jump(x, y, dx, dy, arrival_position) {
+            jumps.push(Jump {
+                index: jump_grid.get_jump_index(index, dx, dy, steps),
+                steps,
+            });
+        }
+    }
+}
+
+/// Precomputed jumps of every node in the eight directions (JPS+),
+/// so the research does not move along the lines node by node.
+///
+/// The table is generated from one state of the grid: it must be
+/// generated again when a node, the movement model or the movement costs
+/// are modified, the researches with an outdated table return
+/// `PathError::UnsupportedGrid`.
+#[derive(Clone, Debug)]
+pub struct JumpTable {
+    width: usize,
+    height: usize,
+    movement: Movement,
+    costs: MoveCosts,
+    revision: u64,
+    distances: Vec<[i32; 8]>,
+}
+
+impl JumpTable {
+
+    /// Generates the jumps of every node of a grid.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid, supported by the jump point research
+    ///
+    /// # Returns:
+    ///
+    /// Created JumpTable object, or `PathError::UnsupportedGrid`.
+    pub fn new(grid: &Grid) -> Result<JumpTable, PathError> {
+
+        if !is_grid_supported(grid) {
+            return Err(PathError::UnsupportedGrid);
+        }
+
+        let jump_grid = JumpGrid::new(grid);
+        let width = grid.get_width();
+        let height = grid.get_height();
+
+        let mut table = JumpTable {
+            width,
+            height,
+            movement: grid.get_movement(),
+            costs: grid.get_move_costs(),
+            revision: grid.get_revision(),
+            distances: vec![[0; 8]; grid.get_nodes_amount()],
+        };
+
+        /* the diagonal jumps use the horizontal and vertical ones */
+        for direction in [0, 2, 4, 6, 1, 3, 5, 7].iter() {
+
+            let (dx, dy) = DIRECTION_OFFSETS[*direction];
+
+            /* the next node in the direction is generated first */
+            for line in 0..height {
+
+                let y = if dy > 0 { height - 1 - line } else { line };
+
+                for column in 0..width {
+
+                    let x = if dx > 0 { width - 1 - column } else { column };
+
+                    let distance = table.generate_distance(
+                        &jump_grid,
+                        x as isize,
+                        y as isize,
+                        *direction,
+                    );
+
+                    table.distances[y * width + x][*direction] = distance;
+                }
+            }
+        }
+
+        Ok(table)
+    }
+
+    /// Generates the jump of one node in one direction,
+    /// the jump of the next node in this direction being already known.
+    ///
+    /// # Arguments:
+    ///
+    /// * `jump_grid` - the movement rules of the grid
+    /// * `x` - the column of the node
+    /// * `y` - the line of the node
+    /// * `direction` - the direction code (see `DIRECTION_OFFSETS`)
+    ///
+    /// # Returns:
+    ///
+    /// The amount of movements to the next jump point, or the opposite
+    /// of the amount of movements before an unusuable node.
+    fn generate_distance(
+        &self,
+        jump_grid: &JumpGrid,
+        x: isize,
+        y: isize,
+        direction: usize,
+    ) -> i32 {
+
+        let (dx, dy) = DIRECTION_OFFSETS[direction];
+
+        if !jump_grid.can_move(x, y, dx, dy) {
+            return 0;
+        }
+
+        let next = ((y + dy) * jump_grid.width + x + dx) as usize;
+        let next_distances = &self.distances[next];
+
+        let is_jump_point = jump_grid.has_forced_neighbour(
+            x + dx,
+            y + dy,
+            dx,
+            dy,
+        ) || (
+            direction % 2 == 1 &&
+            (
+                next_distances[if dx > 0 { 0 } else { 4 }] > 0 ||
+                next_distances[if dy > 0 { 2 } else { 6 }] > 0
+            )
+        );
+
+        if is_jump_point {
+            return 1;
+        }
+
+        let next_distance = next_distances[direction];
+
+        if next_distance > 0 {
+            next_distance + 1
+        } else {
+            next_distance - 1
+        }
+    }
+
+    /// Indicates if the table has been generated from the current state
+    /// of the grid: same dimensions, movement model, movement costs
+    /// and revision.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    ///
+    /// # Returns:
+    ///
+    /// True if the table can be used on the grid.
+    pub fn matches(
+        &self,
+        grid: &Grid,
+    ) -> bool {
+        self.width == grid.get_width() &&
+            self.height == grid.get_height() &&
+            self.movement == grid.get_movement() &&
+            self.costs == grid.get_move_costs() &&
+            self.revision == grid.get_revision()
+    }
+
+    /// Returns the precomputed jump of one node in one direction.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    /// * `direction` - the direction code (see `DIRECTION_OFFSETS`)
+    ///
+    /// # Returns:
+    ///
+    /// The amount of movements to the next jump point, or the opposite
+    /// of the amount of movements before an unusuable node.
+    pub fn get_distance(
+        &self,
+        index: usize,
+        direction: usize,
+    ) -> i32 {
+        self.distances[index][direction]
+    }
+
+    /// Generates the jump points reached from one node of the research
+    /// with the precomputed jumps.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid the table has been generated from
+    /// * `index` - the index of the node
+    /// * `parent` - the index of the jump point the node has been
+    ///   reached from, None for the departure
+    /// * `arrival` - the index of the arrival
+    /// * `jumps` - the list the jump points are written into,
+    ///   its previous content is removed
+    pub fn generate_jumps(
+        &self,
+        grid: &Grid,
+        index: usize,
+        parent: Option<usize>,
+        arrival: usize,
+        jumps: &mut Vec<Jump>,
+    ) {
+
+        let jump_grid = JumpGrid::new(grid);
+        let (x, y) = jump_grid.get_position(index);
+        let (arrival_x, arrival_y) = jump_grid.get_position(arrival);
+
+        let directions = jump_grid.generate_directions(
+            x,
+            y,
+            parent.map(|parent| get_direction(grid, parent, index)),
+        );
+
+        jumps.clear();
+
+        for (direction, &(dx, dy)) in DIRECTION_OFFSETS.iter().enumerate() {
+
+            if directions & (1 << direction) == 0 {
+                continue;
+            }
+
+            let distance = self.distances[index][direction];
+            let reachable = distance.unsigned_abs();
+
+            /* the arrival is reached before the next jump point */
+            let arrival_steps = if dx != 0 && dy != 0 {
+
+                let horizontal = (arrival_x - x) * dx;
+                let vertical = (arrival_y - y) * dy;
+
+                /* the research turns on the line or the column
+                   of the arrival */
+                if horizontal > 0 && vertical > 0 {
+                    Some(horizontal.min(vertical) as u32)
+                } else {
+                    None
+                }
+            } else if dx != 0 && arrival_y == y && (arrival_x - x) * dx > 0 {
+                Some(((arrival_x - x) * dx) as u32)
+            } else if dy != 0 && arrival_x == x && (arrival_y - y) * dy > 0 {
+                Some(((arrival_y - y) * dy) as u32)
+            } else {
+                None
+            };
+
+            let steps = match arrival_steps {
+                Some(steps) if steps <= reachable => steps,
+                _ if distance > 0 => reachable,
+                _ => continue,
+            };
+
+            jumps.push(Jump {
+                index: jump_grid.get_jump_index(index, dx, dy, steps),
+                steps,
+            });
+        }
+    }
+}
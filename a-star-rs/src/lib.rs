@@ -1,88 +1,114 @@
-#![feature(vec_remove_item)]
-#![feature(libc)]
+//! Library that implements the A* algorithm on grids of nodes.
+//!
+//! The grid is a `Nodes` object, its nodes are indexed by row.
+//! Some nodes can be set as unusuable, then the path from
+//! the departure node to the arrival node is researched:
+//!
+//! ```
+//! use astar::Nodes;
+//!
+//! let mut nodes = Nodes::new(6, 6, 13, 34);
+//! nodes.get_node_by_index(27).set_unusuable();
+//!
+//! let path = nodes.research_path().unwrap();
+//! assert_eq!(path.last(), Some(&34));
+//! ```
+//!
+//! The same research is available from C through the functions
+//! of the `ffi` module, declared into `astar.h`.
 
 extern crate libc;
 
 /// One node structure and its implementation.
-mod node;
+pub mod node;
 
 /// Contains all nodes.
-mod nodes;
-
-/// Main interface that calculates and stores the path into the given C array.
-///
-/// # Args:
-///
-/// * `path` - C pointer to the array of indices that are part of the path
-/// * `width` - the number of nodes per line (max allowed: 10),
-/// * `height` - the number of nodes per column (max allowed: 10),
-/// * `departure` - the departure node index,
-/// * `arrival` - the arrival node index
-#[no_mangle]
-pub fn get_path(
-    path: *const libc::uint8_t,
-    walls: *const libc::uint8_t,
-    walls_amount: libc::size_t,
-    width: u8,
-    height: u8,
-    departure: u8,
-    arrival: u8,
-) {
-    const C_PATH_ARRAY_SIZE: usize = 100;
-    let path: &mut [u8] = unsafe {
-        std::slice::from_raw_parts_mut(
-            path as *mut u8,
-            C_PATH_ARRAY_SIZE,
-        )
-    };
-
-    let walls: &[u8] = unsafe {
-        std::slice::from_raw_parts(
-            walls as *const u8,
-            walls_amount as usize,
-        )
-    };
-
-    let mut nodes = nodes::Nodes::new(
-        width,
-        height,
-        departure as usize,
-        arrival as usize,
-    );
-
-    for wall in walls {
-        nodes.get_node_by_index(*wall as usize)
-            .set_unusuable();
-    }
-
-    let path_indices = nodes.research_path();
-
-    for (counter, index) in path_indices.iter().enumerate() {
-        path[counter] = *index as u8;
-    }
-}
-
-/// Returns positions of an index according to the width.
-///
-/// # Arguments:
-///
-/// * `width` - the width of the map
-/// * `index` - the source index
-///
-/// # Returns:
-///
-/// Tuple that contains the horizontal and vertical positions.
-#[no_mangle]
-pub fn get_positions(
-    width: u8,
-    index: u8,
-) -> (u8, u8) {
-
-    (
-        index % width,
-        index / width,
-    )
-}
+pub mod nodes;
+
+/// Map shared by the researches.
+pub mod grid;
+
+/// Memory of one research.
+pub mod search_context;
+
+/// Algorithms of the research.
+pub mod search_algorithm;
+
+/// Distances and directions to the nearest goal.
+pub mod flow_field;
+
+/// Jump point research on uniform cost grids.
+pub mod jump_point;
+
+/// Any-angle research between the centres of the nodes.
+pub mod any_angle;
+
+/// Incremental research repaired after the changes of the grid.
+pub mod d_star_lite;
+
+/// Hierarchical research on clusters of nodes.
+pub mod hierarchical;
+
+/// Traversals of the nodes along a segment.
+pub mod line_of_sight;
+
+/// Waypoints, string pulling and curves along the paths.
+pub mod smoothing;
+
+/// Errors returned by the path research.
+pub mod path_error;
+
+/// Researched paths with their cost and research statistics.
+pub mod path_result;
+
+/// Researches run a few expansions at a time.
+pub mod resumable_search;
+
+/// Estimations of the cost to the arrival node.
+pub mod heuristic;
+
+/// Movement models between the nodes.
+pub mod movement;
+
+/// Costs of the movements between the nodes.
+pub mod move_costs;
+
+/// Coordinates of the nodes.
+pub mod point;
+
+/// C interface of the library.
+pub mod ffi;
+
+pub use ffi::{
+    get_path,
+    get_path_with_costs,
+    PATH_BUFFER_TOO_SMALL,
+    PATH_FOUND,
+};
+pub use any_angle::{
+    AnyAngleAlgorithm,
+    AnyAnglePath,
+};
+pub use d_star_lite::DStarLite;
+pub use flow_field::FlowField;
+pub use grid::Grid;
+pub use hierarchical::HierarchicalGrid;
+pub use jump_point::JumpTable;
+pub use heuristic::Heuristic;
+pub use move_costs::MoveCosts;
+pub use movement::Movement;
+pub use node::Node;
+pub use nodes::Nodes;
+pub use path_error::PathError;
+pub use path_result::PathResult;
+pub use resumable_search::{
+    Progress,
+    ResumableSearch,
+};
+pub use point::Point;
+pub use search_algorithm::SearchAlgorithm;
+pub use search_context::SearchContext;
+pub use smoothing::Curve;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,166 @@
+//! Traversals of the nodes along the segment between two node centres.
+
+use point::Point;
+
+/// Returns the nodes of the line between two nodes drawn with
+/// the Bresenham algorithm: one node per column or per line
+/// (the longest side), every node being a neighbour of the previous one.
+///
+/// # Arguments:
+///
+/// * `from` - the first node of the line
+/// * `to` - the last node of the line
+///
+/// # Returns:
+///
+/// The nodes of the line, from the first one to the last one.
+pub fn bresenham_line(
+    from: Point,
+    to: Point,
+) -> Vec<Point> {
+
+    let (mut x, mut y) = (from.x as i64, from.y as i64);
+    let (to_x, to_y) = (to.x as i64, to.y as i64);
+
+    let (horizontal_steps, vertical_steps) =
+        ((to_x - x).abs(), -(to_y - y).abs());
+    let (horizontal_sign, vertical_sign) =
+        ((to_x - x).signum(), (to_y - y).signum());
+
+    let mut error = horizontal_steps + vertical_steps;
+    let mut line = vec![from];
+
+    while (x, y) != (to_x, to_y) {
+
+        let double_error = 2 * error;
+
+        if double_error >= vertical_steps {
+            error += vertical_steps;
+            x += horizontal_sign;
+        }
+
+        if double_error <= horizontal_steps {
+            error += horizontal_steps;
+            y += vertical_sign;
+        }
+
+        line.push(Point::new(x as usize, y as usize));
+    }
+
+    line
+}
+
+/// Returns every node the segment between the centres of two nodes
+/// goes through (supercover line). When the segment goes exactly
+/// through the corner of two nodes, they are both listed
+/// before the next node.
+///
+/// # Arguments:
+///
+/// * `from` - the first node of the line
+/// * `to` - the last node of the line
+///
+/// # Returns:
+///
+/// The nodes of the line, from the first one to the last one.
+pub fn supercover_line(
+    from: Point,
+    to: Point,
+) -> Vec<Point> {
+
+    let mut line = Vec::new();
+
+    walk_supercover_line(
+        from,
+        to,
+        |point, corner| {
+
+            if let Some((horizontal, vertical)) = corner {
+                line.push(horizontal);
+                line.push(vertical);
+            }
+
+            line.push(point);
+            true
+        },
+    );
+
+    line
+}
+
+/// Visits the nodes the segment between the centres of two nodes
+/// goes through, from the first one to the last one.
+///
+/// # Arguments:
+///
+/// * `from` - the first node of the line
+/// * `to` - the last node of the line
+/// * `visit` - called with every node of the line and, if the segment
+///   enters it exactly through a corner, the horizontal and the vertical
+///   neighbours touching this corner; returns false to stop the traversal
+///
+/// # Returns:
+///
+/// True if every node has been visited, false if the traversal
+/// has been stopped.
+pub fn walk_supercover_line<F>(
+    from: Point,
+    to: Point,
+    mut visit: F,
+) -> bool
+where
+    F: FnMut(Point, Option<(Point, Point)>) -> bool,
+{
+
+    let (mut x, mut y) = (from.x as i64, from.y as i64);
+    let (to_x, to_y) = (to.x as i64, to.y as i64);
+
+    let (horizontal_steps, vertical_steps) =
+        ((to_x - x).abs(), (to_y - y).abs());
+    let (horizontal_sign, vertical_sign) =
+        ((to_x - x).signum(), (to_y - y).signum());
+
+    let get_point = |x: i64, y: i64| Point::new(x as usize, y as usize);
+
+    if !visit(from, None) {
+        return false;
+    }
+
+    let (mut horizontal_step, mut vertical_step) = (0, 0);
+
+    while horizontal_step < horizontal_steps || vertical_step < vertical_steps {
+
+        /* compares the distances to the next vertical border
+           and to the next horizontal border of the current node */
+        let decision =
+            (1 + 2 * horizontal_step) * vertical_steps -
+            (1 + 2 * vertical_step) * horizontal_steps;
+
+        let mut corner = None;
+
+        if decision == 0 {
+
+            corner = Some((
+                get_point(x + horizontal_sign, y),
+                get_point(x, y + vertical_sign),
+            ));
+
+            x += horizontal_sign;
+            y += vertical_sign;
+            horizontal_step += 1;
+            vertical_step += 1;
+        } else if decision < 0 {
+            x += horizontal_sign;
+            horizontal_step += 1;
+        } else {
+            y += vertical_sign;
+            vertical_step += 1;
+        }
+
+        if !visit(get_point(x, y), corner) {
+            return false;
+        }
+    }
+
+    true
+}
@@ -0,0 +1,60 @@
+//! Costs of the movements between a node and its children.
+
+/// Base costs of the horizontal or vertical and diagonal movements,
+/// before the terrain weight of the child is applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MoveCosts {
+    horizontal_or_vertical: u32,
+    diagonal: u32,
+}
+
+impl Default for MoveCosts {
+
+    /// Costs of 10 for horizontal or vertical movements and 14
+    /// for diagonal movements (approximation of 10 * sqrt(2)).
+    fn default() -> MoveCosts {
+        MoveCosts::new(10, 14)
+    }
+}
+
+impl MoveCosts {
+
+    /// Constructor for a new MoveCosts object.
+    ///
+    /// # Arguments:
+    ///
+    /// * `horizontal_or_vertical` - the cost of a movement to
+    ///   a horizontal or vertical child
+    /// * `diagonal` - the cost of a movement to a diagonal child
+    ///
+    /// # Returns:
+    ///
+    /// Created MoveCosts object.
+    pub fn new(
+        horizontal_or_vertical: u32,
+        diagonal: u32,
+    ) -> MoveCosts {
+        MoveCosts {
+            horizontal_or_vertical,
+            diagonal,
+        }
+    }
+
+    /// Getter of the horizontal or vertical movement cost.
+    ///
+    /// # Returns:
+    ///
+    /// The cost of a movement to a horizontal or vertical child.
+    pub fn get_horizontal_or_vertical(&self) -> u32 {
+        self.horizontal_or_vertical
+    }
+
+    /// Getter of the diagonal movement cost.
+    ///
+    /// # Returns:
+    ///
+    /// The cost of a movement to a diagonal child.
+    pub fn get_diagonal(&self) -> u32 {
+        self.diagonal
+    }
+}
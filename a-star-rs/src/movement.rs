@@ -0,0 +1,69 @@
+//! Movement models that define which children a node has.
+
+use heuristic::{
+    Heuristic,
+    Manhattan,
+    Octile,
+};
+
+/// Directions a movement can take from one node to its children.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Movement {
+
+    /// Horizontal and vertical movements only.
+    FourDirections,
+
+    /// Diagonal movements are allowed, even between two unusuable nodes.
+    #[default]
+    EightDirections,
+
+    /// Diagonal movements are forbidden if one of the two horizontal
+    /// and vertical nodes next to them is unusuable.
+    EightDirectionsNoCornerCutting,
+
+    /// Diagonal movements are forbidden only if both the horizontal
+    /// and vertical nodes next to them are unusuable.
+    EightDirectionsNoSqueezing,
+}
+
+impl Movement {
+
+    /// Indicates if a diagonal movement is allowed according to the nodes
+    /// it goes between.
+    ///
+    /// # Arguments:
+    ///
+    /// * `horizontal_usuable` - true if the horizontal neighbour is usuable
+    /// * `vertical_usuable` - true if the vertical neighbour is usuable
+    ///
+    /// # Returns:
+    ///
+    /// True if the diagonal movement is allowed.
+    pub fn is_diagonal_allowed(
+        &self,
+        horizontal_usuable: bool,
+        vertical_usuable: bool,
+    ) -> bool {
+        match *self {
+            Movement::FourDirections => false,
+            Movement::EightDirections => true,
+            Movement::EightDirectionsNoCornerCutting =>
+                horizontal_usuable && vertical_usuable,
+            Movement::EightDirectionsNoSqueezing =>
+                horizontal_usuable || vertical_usuable,
+        }
+    }
+
+    /// Returns the heuristic used when none is explicitly set:
+    /// the manhattan distance without diagonals, the octile distance otherwise.
+    ///
+    /// # Returns:
+    ///
+    /// The default heuristic of the movement.
+    pub fn get_default_heuristic(&self) -> &'static dyn Heuristic {
+        match *self {
+            Movement::FourDirections => &Manhattan,
+            _ => &Octile,
+        }
+    }
+}
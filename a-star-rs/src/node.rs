@@ -1,16 +1,13 @@
 //! One node structure and its implementation.
 
-/// One node on the grid.
-#[allow(dead_code)]
-#[derive(Clone)]
+/// One node on the grid: the map data that does not change
+/// during a research.
+#[derive(Clone, Copy, Debug)]
 pub struct Node {
-    heuristic: u8,
-    cost: u8,
     usuable: bool,
-    backward_movement: i8,
+    weight: u8,
 }
 
-#[allow(dead_code)]
 impl Node {
 
     /// Constructor for a new Node object.
@@ -20,10 +17,8 @@ impl Node {
     /// Created Node object.
     pub fn new() -> Node {
         Node {
-            heuristic: 0,
-            cost: 0,
             usuable: true,
-            backward_movement: 0,
+            weight: 1,
         }
     }
 
@@ -32,75 +27,67 @@ impl Node {
         self.usuable = false;
     }
 
-    /// Indicates if the node is usuable or not.
-    ///
-    /// # Returns:
-    ///
-    /// True if the node is usuable.
-    pub fn is_usuable(&self) -> bool {
-        self.usuable
+    /// Sets the node as usuable again.
+    pub fn set_usuable(&mut self) {
+        self.usuable = true;
     }
 
-    /// Sets the node heuristic.
+    /// Sets the node as unusuable or usuable,
+    /// for example when a door is closed or opened.
     ///
     /// # Arguments:
     ///
-    /// * `heuristic` - the heuristic to set
-    pub fn set_heuristic(
+    /// * `blocked` - true if the node cannot be part of a path
+    pub fn set_blocked(
         &mut self,
-        heuristic: u8,
+        blocked: bool,
     ) {
-        self.heuristic = heuristic;
+        self.usuable = !blocked;
     }
 
-    /// Returns the node heuristic.
+    /// Indicates if the node is usuable or not.
     ///
     /// # Returns:
     ///
-    /// The node heuristic.
-    pub fn get_heuristic(&self) -> u8 {
-        self.heuristic
+    /// True if the node is usuable.
+    pub fn is_usuable(&self) -> bool {
+        self.usuable
     }
 
-    /// Sets the cost.
+    /// Sets the terrain weight, the movement cost to the node
+    /// is multiplied by this value. The default weight is 1.
     ///
     /// # Arguments:
     ///
-    /// * `cost` - the cost to set
-    pub fn set_cost(
+    /// * `weight` - the terrain weight; 0 sets the node as unusuable
+    ///   and keeps its previous weight, as a free movement would make
+    ///   the heuristics overestimate the real cost
+    pub fn set_weight(
         &mut self,
-        cost: u8,
+        weight: u8,
     ) {
-        self.cost = cost;
+
+        if weight == 0 {
+            self.usuable = false;
+            return;
+        }
+
+        self.weight = weight;
     }
 
-    /// Getter of the cost.
+    /// Getter of the terrain weight.
     ///
     /// # Returns:
     ///
-    /// The node movement cost.
-    pub fn get_cost(&self) -> u8 {
-        self.cost
+    /// The terrain weight of the node.
+    pub fn get_weight(&self) -> u8 {
+        self.weight
     }
+}
 
-    /// Setter for the backward movement.
-    ///
-    /// # Arguments:
-    ///
-    /// * `backward_movement` - the backward movement
-    pub fn set_backward_movement(
-        &mut self,
-        backward_movement: i8,
-    ) {
-        self.backward_movement = backward_movement;
-    }
+impl Default for Node {
 
-    /// Getter of the backward movement.
-    ///
-    /// # Returns:
-    ///
-    /// The backward movement of the node.
-    pub fn get_backward_movement(&self) -> i8 {
-        self.backward_movement
+    fn default() -> Node {
+        Node::new()
     }
 }
@@ -1,23 +1,40 @@
 //! Module that contains the grid structure and its implementation.
 
+use any_angle::{
+    research_any_angle_path,
+    AnyAngleAlgorithm,
+    AnyAnglePath,
+};
+use d_star_lite::DStarLite;
+use flow_field::FlowField;
+use grid::Grid;
+use hierarchical::HierarchicalGrid;
+use jump_point::JumpTable;
+use heuristic::Heuristic;
+use move_costs::MoveCosts;
+use movement::Movement;
 use node::Node;
-
-/// Grid that contains all the nodes.
-#[allow(dead_code)]
+use path_error::PathError;
+use path_result::PathResult;
+use point::Point;
+use resumable_search::ResumableSearch;
+use search_algorithm::SearchAlgorithm;
+use search_context::SearchContext;
+
+/// Grid that contains all the nodes, with the departure, the arrival
+/// and the memory of one research.
+///
+/// The map is a `Grid` and the research memory a `SearchContext`,
+/// both can be used directly to share one map between many researches.
 #[derive(Clone)]
 pub struct Nodes {
-    width: u8,
-    height: u8,
-    nodes: Vec<Node>,
+    grid: Grid,
+    search: SearchContext,
     departure_index: usize,
-    current_index: usize,
     arrival_index: usize,
-    open_list: Vec<usize>,
-    closed_list: Vec<usize>,
-    children_list: Vec<usize>,
+    jump_table: Option<JumpTable>,
 }
 
-#[allow(dead_code)]
 impl Nodes {
 
     /// Constructor for a new Nodes object.
@@ -33,283 +50,627 @@ impl Nodes {
     ///
     /// Created Nodes object.
     pub fn new(
-        width: u8,
-        height: u8,
+        width: usize,
+        height: usize,
         departure: usize,
         arrival: usize,
     ) -> Nodes {
 
-        let mut nodes: Vec<Node> = Vec::new();
-        let indices = width * height;
+        let grid = Grid::new(width, height);
 
-        for _ in 0..indices {
-            nodes.push(Node::new());
-        }
+        let mut search = SearchContext::new();
+        search.prepare_research(
+            &grid,
+            departure,
+            arrival,
+        );
 
         Nodes {
-            width: width,
-            height: height,
-            nodes: nodes,
+            grid,
+            search,
             departure_index: departure,
-            current_index: departure,
             arrival_index: arrival,
-            open_list: Vec::new(),
-            closed_list: vec![departure],
-            children_list: Vec::new(),
+            jump_table: None,
         }
     }
 
-    /// Main research method
-    pub fn research_path(&mut self) -> Vec<usize> {
-
-        let mut final_index: Option<usize> = None;
+    /// Constructor for a new Nodes object from the coordinates
+    /// of the departure and the arrival.
+    ///
+    /// # Arguments:
+    ///
+    /// * `width` - the number of nodes per line,
+    /// * `height` - the number of lines
+    /// * `departure` - the departure coordinates
+    /// * `arrival` - the arrival coordinates
+    ///
+    /// # Returns:
+    ///
+    /// Created Nodes object, or an error if the departure
+    /// or the arrival is outside of the grid.
+    pub fn from_points(
+        width: usize,
+        height: usize,
+        departure: Point,
+        arrival: Point,
+    ) -> Result<Nodes, PathError> {
+
+        let grid = Grid::new(width, height);
+        let departure = grid.get_index(departure)?;
+        let arrival = grid.get_index(arrival)?;
+
+        Ok(Nodes::new(
+            width,
+            height,
+            departure,
+            arrival,
+        ))
+    }
 
-        self.generate_heuristics();
-        self.generate_children_list();
+    /// Getter of the grid.
+    ///
+    /// # Returns:
+    ///
+    /// The map the researches run on.
+    pub fn get_grid(&self) -> &Grid {
+        &self.grid
+    }
 
-        while final_index.is_none() {
+    /// Getter of the research memory.
+    ///
+    /// # Returns:
+    ///
+    /// The costs, parents, open list and closed list of the research.
+    pub fn get_search_context(&self) -> &SearchContext {
+        &self.search
+    }
 
-            self.update_open_list();
-            self.generate_costs();
+    /// Getter of the research memory for write access.
+    ///
+    /// # Returns:
+    ///
+    /// The costs, parents, open list and closed list of the research.
+    pub fn get_search_context_mut(&mut self) -> &mut SearchContext {
+        &mut self.search
+    }
 
-            final_index = self.iterate();
+    /// Sets the heuristic used to estimate the cost to the arrival node.
+    /// By default, the heuristic depends on the movement model.
+    ///
+    /// # Arguments:
+    ///
+    /// * `heuristic` - the heuristic, or a closure taking the horizontal
+    ///   and vertical distances to the arrival node and the movement costs
+    pub fn set_heuristic<H: Heuristic + 'static>(
+        &mut self,
+        heuristic: H,
+    ) {
+        self.grid.set_heuristic(heuristic);
+    }
 
-            self.generate_children_list();
-            self.generate_backward_movement();
-        }
+    /// Sets the terrain weight of one node.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    /// * `weight` - the terrain weight, the movement cost to the node
+    ///   is multiplied by this value; 0 sets the node as unusuable
+    ///
+    /// # Returns:
+    ///
+    /// An error if the index is outside of the grid.
+    pub fn set_weight(
+        &mut self,
+        index: usize,
+        weight: u8,
+    ) -> Result<(), PathError> {
+        self.jump_table = None;
+        self.grid.set_weight(index, weight)
+    }
 
-        let arrival_index = self.arrival_index;
-        self.get_node_by_index(arrival_index)
-            .set_backward_movement(
-                final_index.unwrap() as i8 -
-                arrival_index as i8
-            );
+    /// Sets one node as unusuable or usuable.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    /// * `blocked` - true if the node cannot be part of a path
+    ///
+    /// # Returns:
+    ///
+    /// An error if the index is outside of the grid.
+    pub fn set_blocked(
+        &mut self,
+        index: usize,
+        blocked: bool,
+    ) -> Result<(), PathError> {
+        self.jump_table = None;
+        self.grid.set_blocked(index, blocked)
+    }
 
-        let mut path = Vec::new();
-        let mut current_index = self.arrival_index;
+    /// Sets the terrain weights of all the nodes at once.
+    ///
+    /// # Arguments:
+    ///
+    /// * `weights` - one weight per node, ordered by index;
+    ///   a weight of 0 sets the node as unusuable,
+    ///   the other ones set the node as usuable
+    ///
+    /// # Returns:
+    ///
+    /// An error if there is not exactly one weight per node.
+    pub fn set_weights(
+        &mut self,
+        weights: &[u8],
+    ) -> Result<(), PathError> {
+        self.jump_table = None;
+        self.grid.set_weights(weights)
+    }
 
-        while current_index != self.departure_index {
+    /// Sets the base costs of the movements. The default heuristics
+    /// are scaled to these costs.
+    ///
+    /// # Arguments:
+    ///
+    /// * `costs` - the horizontal or vertical and diagonal movement costs
+    pub fn set_move_costs(
+        &mut self,
+        costs: MoveCosts,
+    ) {
+        self.jump_table = None;
+        self.grid.set_move_costs(costs);
+    }
 
-            path.push(current_index);
+    /// Getter of the movement costs.
+    ///
+    /// # Returns:
+    ///
+    /// The base costs of the movements.
+    pub fn get_move_costs(&self) -> MoveCosts {
+        self.grid.get_move_costs()
+    }
 
-            current_index = (
-                current_index as i8 +
-                self.get_node_by_index(current_index)
-                    .get_backward_movement()
-            ) as usize;
-        }
+    /// Sets the movement model that defines the children of every node.
+    /// All the eight directions are allowed by default.
+    ///
+    /// # Arguments:
+    ///
+    /// * `movement` - the movement model
+    pub fn set_movement(
+        &mut self,
+        movement: Movement,
+    ) {
+        self.jump_table = None;
+        self.grid.set_movement(movement);
+    }
 
-        path.reverse();
+    /// Getter of the movement model.
+    ///
+    /// # Returns:
+    ///
+    /// The movement model of the grid.
+    pub fn get_movement(&self) -> Movement {
+        self.grid.get_movement()
+    }
 
-        path
+    /// Sets the algorithm of the next researches, A* by default.
+    ///
+    /// # Arguments:
+    ///
+    /// * `algorithm` - the research algorithm
+    pub fn set_algorithm(
+        &mut self,
+        algorithm: SearchAlgorithm,
+    ) {
+        self.search.set_algorithm(algorithm);
     }
 
-    /// Generate the heuristics of every node from departure and arrival.
-    pub fn generate_heuristics(&mut self) {
+    /// Getter of the research algorithm.
+    ///
+    /// # Returns:
+    ///
+    /// The algorithm of the researches.
+    pub fn get_algorithm(&self) -> SearchAlgorithm {
+        self.search.get_algorithm()
+    }
 
-        let (
-            index_x,
-            index_y,
-        ) = self.get_positions(self.arrival_index);
+    /// Setter of the departure index, used from the next research
+    /// or the next search reset.
+    ///
+    /// # Arguments:
+    ///
+    /// * `departure` - the departure cell index
+    pub fn set_departure_index(
+        &mut self,
+        departure: usize,
+    ) {
+        self.departure_index = departure;
+    }
 
-        let nodes_copy = self.clone();
+    /// Getter of the departure index.
+    ///
+    /// # Returns:
+    ///
+    /// The departure cell index.
+    pub fn get_departure_index(&self) -> usize {
+        self.departure_index
+    }
 
-        for (counter, node) in self.nodes.iter_mut().enumerate() {
+    /// Setter of the arrival index, used from the next research
+    /// or the next search reset.
+    ///
+    /// # Arguments:
+    ///
+    /// * `arrival` - the arrival cell index
+    pub fn set_arrival_index(
+        &mut self,
+        arrival: usize,
+    ) {
+        self.arrival_index = arrival;
+    }
 
-            let (
-                node_x,
-                node_y,
-            ) = nodes_copy.get_positions(counter);
+    /// Getter of the arrival index.
+    ///
+    /// # Returns:
+    ///
+    /// The arrival cell index.
+    pub fn get_arrival_index(&self) -> usize {
+        self.arrival_index
+    }
 
-            /* rounded at the integer level */
-            let heuristic = (
-                ((index_x as i8 - node_x as i8) as f32).powi(2) +
-                ((index_y as i8 - node_y as i8) as f32).powi(2)
-            ).sqrt() as u8;
+    /// Clears the open list, the closed list, the costs and the parents
+    /// of the previous research, in constant time. The departure
+    /// is added to the closed list, like after the creation of the grid.
+    ///
+    /// `research_path` resets the search by itself, this is only required
+    /// before researching step by step.
+    pub fn reset_search(&mut self) {
+        self.search.prepare_research(
+            &self.grid,
+            self.departure_index,
+            self.arrival_index,
+        );
+    }
 
-            (*node).set_heuristic(heuristic);
+    /// Main research method
+    ///
+    /// Runs the research algorithm (A* by default) from the departure node
+    /// until the arrival node is taken from the open list, then rebuilds
+    /// the path by following the parents back to the departure.
+    ///
+    /// The jump point research uses the precomputed jumps
+    /// if `generate_jump_table` has been called since the last change
+    /// of the grid.
+    ///
+    /// # Returns:
+    ///
+    /// The indices of the path nodes, departure excluded,
+    /// or the reason why there is no path.
+    pub fn research_path(&mut self) -> Result<Vec<usize>, PathError> {
+
+        if let Some(ref table) = self.jump_table {
+
+            if self.search.get_algorithm() == SearchAlgorithm::JumpPoint {
+                return self.search.research_path_with_jump_table(
+                    &self.grid,
+                    table,
+                    self.departure_index,
+                    self.arrival_index,
+                );
+            }
         }
+
+        self.search.research_path(
+            &self.grid,
+            self.departure_index,
+            self.arrival_index,
+        )
     }
 
-    /// Generates the list of children for the current index.
-    pub fn generate_children_list(&mut self) {
+    /// Researches the path like `research_path` and returns it
+    /// with its cost, its length and the statistics of the research.
+    ///
+    /// # Arguments:
+    ///
+    /// * `keep_closed_list` - true to return the closed list,
+    ///   for debugging
+    ///
+    /// # Returns:
+    ///
+    /// The path and its research statistics,
+    /// or the reason why there is no path.
+    pub fn research_path_result(
+        &mut self,
+        keep_closed_list: bool,
+    ) -> Result<PathResult, PathError> {
 
-        let mut children: Vec<usize> = Vec::new();
+        let path = self.research_path()?;
 
-        let (
-            horizontal_position,
-            vertical_position,
-        ) = self.get_positions(self.current_index);
+        Ok(self.search.get_path_result(
+            &self.grid,
+            path,
+            keep_closed_list,
+        ))
+    }
 
-        if horizontal_position != 0 {
-            children.push(
-                self.get_index_from_positions(
-                    horizontal_position - 1,
-                    vertical_position,
-                )
-            );
-        }
+    /// Researches the path like `research_path`
+    /// and returns the coordinates of its nodes.
+    ///
+    /// # Returns:
+    ///
+    /// The coordinates of the path nodes, departure excluded,
+    /// or the reason why there is no path.
+    pub fn research_path_points(&mut self) -> Result<Vec<Point>, PathError> {
 
-        if horizontal_position != self.width - 1 {
-            children.push(
-                self.get_index_from_positions(
-                    horizontal_position + 1,
-                    vertical_position,
-                )
-            );
-        }
+        let path = self.research_path()?;
 
-        if vertical_position != 0 {
-            children.push(
-                self.get_index_from_positions(
-                    horizontal_position,
-                    vertical_position - 1,
-                )
-            );
-
-            if horizontal_position != self.width - 1 {
-                children.push(
-                    self.get_index_from_positions(
-                        horizontal_position + 1,
-                        vertical_position - 1,
-                    )
-                );
-            }
+        path.into_iter()
+            .map(|index| self.grid.get_point(index))
+            .collect()
+    }
 
-            if horizontal_position != 0 {
-                children.push(
-                    self.get_index_from_positions(
-                        horizontal_position - 1,
-                        vertical_position - 1,
-                    )
-                );
-            }
-        }
+    /// Generates the distance to the nearest goal and the direction
+    /// of the next movement for every node, so many units can read
+    /// their next movement without researching their own path.
+    ///
+    /// # Arguments:
+    ///
+    /// * `goals` - the indices of the goals, the unusuable ones are ignored
+    ///
+    /// # Returns:
+    ///
+    /// The flow field, or an error if a goal is outside of the grid.
+    pub fn generate_flow_field(
+        &self,
+        goals: &[usize],
+    ) -> Result<FlowField, PathError> {
+        FlowField::new(&self.grid, goals)
+    }
 
-        if vertical_position != self.height - 1 {
-            children.push(
-                self.get_index_from_positions(
-                    horizontal_position,
-                    vertical_position + 1,
-                )
-            );
-
-            if horizontal_position != self.width - 1 {
-                children.push(
-                    self.get_index_from_positions(
-                        horizontal_position + 1,
-                        vertical_position + 1,
-                    )
-                );
-            }
+    /// Indicates if the segment between the centres of two nodes only goes
+    /// through usuable nodes (see `Grid::has_line_of_sight`).
+    ///
+    /// # Arguments:
+    ///
+    /// * `from` - the index of the first node
+    /// * `to` - the index of the second node
+    ///
+    /// # Returns:
+    ///
+    /// True if the second node can be seen from the first one,
+    /// or an error if an index is outside of the grid.
+    pub fn has_line_of_sight(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<bool, PathError> {
+        self.grid.has_line_of_sight(
+            from,
+            to,
+        )
+    }
 
-            if horizontal_position != 0 {
-                children.push(
-                    self.get_index_from_positions(
-                        horizontal_position - 1,
-                        vertical_position + 1,
-                    )
-                );
-            }
-        }
+    /// Follows a ray from the centre of one node until it reaches
+    /// an unusuable node (see `Grid::raycast`).
+    ///
+    /// # Arguments:
+    ///
+    /// * `from` - the index of the node the ray starts from
+    /// * `direction_x` - the horizontal component of the direction
+    /// * `direction_y` - the vertical component of the direction
+    /// * `max_distance` - the length of the ray
+    ///
+    /// # Returns:
+    ///
+    /// The first unusuable node reached by the ray, None if there is none,
+    /// or an error if the index is outside of the grid.
+    pub fn raycast(
+        &self,
+        from: usize,
+        direction_x: f64,
+        direction_y: f64,
+        max_distance: f64,
+    ) -> Result<Option<usize>, PathError> {
+        self.grid.raycast(
+            from,
+            direction_x,
+            direction_y,
+            max_distance,
+        )
+    }
 
-        self.children_list = children;
+    /// Researches a path from the departure to the arrival whose segments
+    /// can have any angle (see `any_angle::research_any_angle_path`).
+    ///
+    /// # Arguments:
+    ///
+    /// * `algorithm` - the variant of the any-angle research
+    ///
+    /// # Returns:
+    ///
+    /// The waypoints and the euclidean length of the path,
+    /// or the reason why there is no path.
+    pub fn research_any_angle_path(
+        &self,
+        algorithm: AnyAngleAlgorithm,
+    ) -> Result<AnyAnglePath, PathError> {
+        research_any_angle_path(
+            &self.grid,
+            self.departure_index,
+            self.arrival_index,
+            algorithm,
+        )
     }
 
-    /// Returns the children open list.
+    /// Creates an incremental planner from the departure to the arrival.
+    /// After every change of a node, `DStarLite::notify_cell_changed`
+    /// must be called with the grid, then the path can be researched
+    /// again from the new position of the unit.
     ///
     /// # Returns:
     ///
-    /// Vector that contains the indices of all the children.
-    pub fn get_open_list(&self) -> Vec<usize> {
-        self.open_list.clone()
+    /// The planner, or an error if the departure or the arrival
+    /// is outside of the grid.
+    pub fn create_planner(&self) -> Result<DStarLite, PathError> {
+        DStarLite::new(
+            &self.grid,
+            self.departure_index,
+            self.arrival_index,
+        )
     }
 
-    /// Generates the costs of the open list nodes.
-    pub fn generate_costs(&mut self) {
+    /// Starts a research from the departure to the arrival with the
+    /// algorithm of the grid, that is then run a few expansions at a time
+    /// by `ResumableSearch::step` with the grid. The grid must not be
+    /// modified until the research is over, it must be restarted otherwise.
+    ///
+    /// # Returns:
+    ///
+    /// The started research.
+    pub fn create_resumable_search(&self) -> ResumableSearch {
+        ResumableSearch::new(
+            &self.grid,
+            self.departure_index,
+            self.arrival_index,
+            self.search.get_algorithm(),
+        )
+    }
 
-        let signed_current = self.current_index as i8;
-        let current_cost = self.nodes[self.current_index].get_cost();
+    /// Splits the grid into clusters and builds the graph of their
+    /// entrances for hierarchical researches. After every change
+    /// of a node, `HierarchicalGrid::notify_cell_changed` must be called
+    /// with the grid.
+    ///
+    /// # Arguments:
+    ///
+    /// * `cluster_size` - the number of nodes per line and per column
+    ///   of a cluster
+    ///
+    /// # Returns:
+    ///
+    /// The abstract graph of the grid.
+    pub fn create_hierarchy(
+        &self,
+        cluster_size: usize,
+    ) -> HierarchicalGrid {
+        HierarchicalGrid::new(
+            &self.grid,
+            cluster_size,
+        )
+    }
 
-        for index in self.open_list.iter() {
+    /// Precomputes the jumps of every node for the next jump point
+    /// researches (JPS+). The table is dropped as soon as a node,
+    /// the terrain weights, the movement costs or the movement model
+    /// is modified.
+    ///
+    /// # Returns:
+    ///
+    /// `PathError::UnsupportedGrid` if the jump point research
+    /// does not support the grid.
+    pub fn generate_jump_table(&mut self) -> Result<(), PathError> {
+        self.jump_table = Some(JumpTable::new(&self.grid)?);
+        Ok(())
+    }
 
-            let signed_index = *index as i8;
-            let node = &mut self.nodes[*index];
+    /// Returns the amount of nodes expanded by the last research
+    /// from the side of the departure.
+    ///
+    /// # Returns:
+    ///
+    /// The amount of nodes whose children have been added
+    /// to the open list.
+    pub fn get_departure_side_expansions(&self) -> usize {
+        self.search.get_departure_side_expansions()
+    }
 
-            if node.get_cost() != 0 {
-                continue;
-            }
+    /// Returns the amount of nodes expanded by the last research
+    /// from the side of the arrival.
+    ///
+    /// # Returns:
+    ///
+    /// The amount of expanded nodes, 0 if the research
+    /// is not bidirectional.
+    pub fn get_arrival_side_expansions(&self) -> usize {
+        self.search.get_arrival_side_expansions()
+    }
 
-            const DIAGONAL_MOVE: u8 = 14;
-            let mut cost = DIAGONAL_MOVE;
+    /// Generate the heuristics of every node from departure and arrival.
+    ///
+    /// The research only generates the heuristic of a node
+    /// when this one is reached for the first time.
+    pub fn generate_heuristics(&mut self) {
+        self.search.generate_heuristics(&self.grid);
+    }
 
-            const HORIZONTAL_MOVE: i8 = 1;
-            let map_width = self.width as i8;
+    /// Generates the list of children for the current index.
+    ///
+    /// Diagonal children are added according to the movement model.
+    pub fn generate_children_list(&mut self) {
+        self.search.generate_children_list(&self.grid);
+    }
 
-            if signed_index == signed_current - HORIZONTAL_MOVE ||
-                signed_index == signed_current + HORIZONTAL_MOVE ||
-                signed_index == signed_current - map_width ||
-                signed_index == signed_current + map_width {
+    /// Returns the children open list.
+    ///
+    /// # Returns:
+    ///
+    /// Vector that contains the indices of all the open list nodes,
+    /// sorted in ascending order.
+    pub fn get_open_list(&self) -> Vec<usize> {
+        self.search.get_open_list()
+    }
 
-                const HORIZONTAL_OR_VERTICAL_MOVE: u8 = 10;
-                cost = HORIZONTAL_OR_VERTICAL_MOVE;
-            }
+    /// Generates the costs of the children that are in the open list.
+    ///
+    /// The cost of a movement is multiplied by the terrain weight
+    /// of the child.
+    ///
+    /// A child reached for the first time takes the current node
+    /// as parent. A child already in the open list is updated
+    /// only if going through the current node is cheaper.
+    /// Every new or lowered cost is pushed into the open list.
+    pub fn generate_costs(&mut self) {
+        self.search.generate_costs(&self.grid);
+    }
 
-            node.set_cost(current_cost + cost);
-        }
+    /// Returns the cost of the movement from one node to one of its children.
+    ///
+    /// # Arguments:
+    ///
+    /// * `from` - the index of the node
+    /// * `to` - the index of the child
+    ///
+    /// # Returns:
+    ///
+    /// The base movement cost multiplied by the terrain weight of the child.
+    pub fn get_movement_cost(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> u32 {
+        self.grid.get_movement_cost(from, to)
     }
 
     /// Getter for the closed list.
     ///
     /// # Returns:
     ///
-    /// Vector containing the closed list.
+    /// Vector containing the closed list, sorted in ascending order.
     pub fn get_closed_list(&self) -> Vec<usize> {
-        self.closed_list.clone()
+        self.search.get_closed_list()
     }
 
     /// Iterates to the next node and remove the target from the open list.
     ///
+    /// The open list node with the smallest sum of cost and heuristic
+    /// becomes the current node and is moved into the closed list.
+    /// On equal sums, the node with the smallest heuristic is chosen.
+    ///
     /// Returns:
     ///
-    /// The index of the final node if found, or None
+    /// The index of the arrival node if it is the new current node,
+    /// or None (also when the open list is empty)
     pub fn iterate(&mut self) -> Option<usize> {
-
-        // FIXME: #55 limits the capacities of the algorithm,
-        // check if there is a better way to handle this `initial` value
-        let mut minimum: u8 = <u8>::max_value();
-
-        let mut target: usize = 0;
-
-        for index in self.open_list.iter() {
-
-            let node = &self.nodes[*index];
-            let heuristic = node.get_heuristic();
-
-            if heuristic == 1 {
-                self.current_index = *index;
-                return Some(*index);
-            }
-
-            let value = heuristic + node.get_cost();
-
-            if value < minimum {
-                minimum = value;
-                target = *index;
-            }
-        }
-
-        // FIXME: #55 incorrect behaviour if no path is found
-        self.current_index = target;
-
-        // FIXME: #60 check if going to an open list node from the new current
-        // node is faster than going from the previous current node
-        // to this open list node
-
-        self.open_list.remove_item(&target);
-        self.closed_list.push(target);
-
-        None
+        self.search.iterate()
     }
 
     /// Getter for the current index.
@@ -318,7 +679,7 @@ impl Nodes {
     ///
     /// The current index.
     pub fn get_current_index(&self) -> usize {
-        self.current_index
+        self.search.get_current_index()
     }
 
     /// Setter of the current index.
@@ -330,24 +691,12 @@ impl Nodes {
         &mut self,
         current: usize,
     ) {
-        self.current_index = current;
+        self.search.set_current_index(current);
     }
 
     /// Sets the open list as the children list.
     pub fn update_open_list(&mut self) {
-
-        for index in self.children_list.iter() {
-
-            if
-                self.open_list.contains(index) ||
-                self.closed_list.contains(index) ||
-                !self.nodes[*index].is_usuable()
-            {
-                continue;
-            }
-
-            self.open_list.push(*index);
-        }
+        self.search.update_open_list(&self.grid);
     }
 
     /// Returns the children list.
@@ -356,7 +705,16 @@ impl Nodes {
     ///
     /// List of the current children.
     pub fn get_children_list(&self) -> Vec<usize> {
-        self.children_list.clone()
+        self.search.get_children_list()
+    }
+
+    /// Getter of the amount of nodes into the grid.
+    ///
+    /// # Returns:
+    ///
+    /// The width multiplied by the height.
+    pub fn get_nodes_amount(&self) -> usize {
+        self.grid.get_nodes_amount()
     }
 
     /// Returns a node reference of a node for read and write access.
@@ -372,79 +730,107 @@ impl Nodes {
         &mut self,
         index: usize,
     ) -> &mut Node {
-        &mut self.nodes[index]
+        self.jump_table = None;
+        self.grid.get_node_by_index(index)
     }
 
-    /// Generates the backward movement of the current index.
-    pub fn generate_backward_movement(&mut self) {
-
-        let current_index = self.current_index as i8;
-        let mut selected_index = self.departure_index as i8;
-
-        let children_list = self.children_list.clone();
-        if !children_list.contains(&self.departure_index) {
-
-            let mut minimum_cost = <u8>::max_value();
-
-            for child in children_list.iter() {
-
-                let child_node = self.get_node_by_index(*child);
-                let child_cost = child_node.get_cost();
-
-                if
-                    child_cost != 0 &&
-                    child_cost < minimum_cost
-                {
-                    minimum_cost = child_cost;
-                    selected_index = *child as i8;
-                }
-            }
-        }
-
-        let index = self.current_index;
-        let mut current_node = self.get_node_by_index(index);
+    /// Returns a node from its coordinates for read and write access.
+    ///
+    /// # Arguments:
+    ///
+    /// * `x` - the column of the node
+    /// * `y` - the line of the node
+    ///
+    /// # Returns:
+    ///
+    /// The node to read or write, or an error if it is outside of the grid.
+    pub fn node_at(
+        &mut self,
+        x: usize,
+        y: usize,
+    ) -> Result<&mut Node, PathError> {
+        self.jump_table = None;
+        self.grid.node_at(x, y)
+    }
 
-        let backward_movement = selected_index - current_index;
-        current_node.set_backward_movement(backward_movement);
+    /// Converts coordinates into an index.
+    ///
+    /// # Arguments:
+    ///
+    /// * `point` - the coordinates of the node
+    ///
+    /// # Returns:
+    ///
+    /// The index of the node, or an error if it is outside of the grid.
+    pub fn get_index(
+        &self,
+        point: Point,
+    ) -> Result<usize, PathError> {
+        self.grid.get_index(point)
     }
 
-    /// Returns the horizontal and vertical position for the given index.
+    /// Converts an index into coordinates.
     ///
     /// # Arguments:
     ///
-    /// * `index` - the source index
+    /// * `index` - the index of the node
     ///
     /// # Returns:
     ///
-    /// Tuple that contains the horizontal and vertical positions.
-    fn get_positions(
+    /// The coordinates of the node, or an error if it is outside of the grid.
+    pub fn get_point(
         &self,
         index: usize,
-    ) -> (u8, u8) {
+    ) -> Result<Point, PathError> {
+        self.grid.get_point(index)
+    }
 
-        let index = index as u8;
+    /// Returns the heuristic of one node.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The estimated cost to the arrival, 0 if not generated yet.
+    pub fn get_heuristic(
+        &self,
+        index: usize,
+    ) -> u32 {
+        self.search.get_heuristic(index)
+    }
 
-        (
-            index % self.width,
-            index / self.width,
-        )
+    /// Returns the cost of one node.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The cost of the cheapest known path from the departure to the node.
+    pub fn get_cost(
+        &self,
+        index: usize,
+    ) -> u32 {
+        self.search.get_cost(index)
     }
 
-    /// Returns the index according to the horizontal and vertical positions.
+    /// Returns the parent of one node.
     ///
     /// # Arguments:
     ///
-    /// * `horizontal_position` - the horizontal position
-    /// * `vertical_position` - the vertical position
+    /// * `index` - the index of the node
     ///
     /// # Returns:
     ///
-    /// The index from the given positions.
-    fn get_index_from_positions(
+    /// The index of the previous node on the cheapest known path,
+    /// or None if the node has not been reached yet.
+    pub fn get_parent(
         &self,
-        horizontal_position: u8,
-        vertical_position: u8,
-    ) -> usize {
-        (vertical_position * self.width + horizontal_position) as usize
+        index: usize,
+    ) -> Option<usize> {
+        self.search.get_parent(index)
     }
 }
@@ -0,0 +1,83 @@
+//! Errors that can be returned by the path research.
+
+use std::error::Error;
+use std::fmt;
+
+/// Reasons why no path can be returned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathError {
+
+    /// Every reachable node has been explored without finding the arrival.
+    Unreachable,
+
+    /// The departure node is set as unusuable.
+    DepartureBlocked,
+
+    /// The arrival node is set as unusuable.
+    ArrivalBlocked,
+
+    /// An index is outside of the grid.
+    OutOfBounds,
+
+    /// The departure and the arrival are the same node,
+    /// so the path is empty.
+    DepartureIsArrival,
+
+    /// The research algorithm does not support the movement model,
+    /// the movement costs or the terrain weights of the grid.
+    UnsupportedGrid,
+
+    /// The research has been cancelled before its end.
+    Cancelled,
+
+    /// The cost of a path from the departure does not fit into a `u32`.
+    CostOverflow,
+}
+
+impl PathError {
+
+    /// Returns the status code of the error for the C interface.
+    ///
+    /// # Returns:
+    ///
+    /// A strictly positive status code, 0 being used when a path is found
+    /// (6 and 7 are used by the C interface for its own errors).
+    pub fn get_status_code(&self) -> i32 {
+        match *self {
+            PathError::Unreachable => 1,
+            PathError::DepartureBlocked => 2,
+            PathError::ArrivalBlocked => 3,
+            PathError::OutOfBounds => 4,
+            PathError::DepartureIsArrival => 5,
+            PathError::UnsupportedGrid => 8,
+            PathError::Cancelled => 9,
+            PathError::CostOverflow => 10,
+        }
+    }
+}
+
+impl fmt::Display for PathError {
+
+    fn fmt(
+        &self,
+        formatter: &mut fmt::Formatter,
+    ) -> fmt::Result {
+
+        let message = match *self {
+            PathError::Unreachable => "the arrival cannot be reached",
+            PathError::DepartureBlocked => "the departure is not usuable",
+            PathError::ArrivalBlocked => "the arrival is not usuable",
+            PathError::OutOfBounds => "the index is outside of the grid",
+            PathError::DepartureIsArrival =>
+                "the departure is the arrival, the path is empty",
+            PathError::UnsupportedGrid =>
+                "the research algorithm does not support the grid",
+            PathError::Cancelled => "the research has been cancelled",
+            PathError::CostOverflow => "the cost of the path overflows",
+        };
+
+        formatter.write_str(message)
+    }
+}
+
+impl Error for PathError {}
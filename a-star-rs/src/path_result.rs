@@ -0,0 +1,159 @@
+//! Researched path with its cost, its length and the statistics
+//! of the research.
+
+use grid::Grid;
+
+/// Path found by a research, with what it costs and what it took
+/// to find it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathResult {
+    path: Vec<usize>,
+    cost: u32,
+    length: f64,
+    expanded_amount: usize,
+    generated_amount: usize,
+    peak_open_amount: usize,
+    closed_list: Option<Vec<usize>>,
+}
+
+impl PathResult {
+
+    /// Constructor for a new PathResult object.
+    /// The cost and the length are calculated along the path.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    /// * `departure` - the departure cell index
+    /// * `path` - the indices of the path nodes, departure excluded
+    /// * `expanded_amount` - the amount of nodes whose children
+    ///   have been added to the open list
+    /// * `generated_amount` - the amount of nodes added to the open list
+    /// * `peak_open_amount` - the largest amount of nodes
+    ///   into the open list at once
+    /// * `closed_list` - the closed nodes, if kept for debugging
+    ///
+    /// # Returns:
+    ///
+    /// Created PathResult object.
+    pub fn new(
+        grid: &Grid,
+        departure: usize,
+        path: Vec<usize>,
+        expanded_amount: usize,
+        generated_amount: usize,
+        peak_open_amount: usize,
+        closed_list: Option<Vec<usize>>,
+    ) -> PathResult {
+
+        let width = grid.get_width();
+        let mut cost: u32 = 0;
+        let mut length = 0.0;
+        let mut previous = departure;
+
+        for index in path.iter() {
+
+            let horizontal = (index % width) as f64 - (previous % width) as f64;
+            let vertical = (index / width) as f64 - (previous / width) as f64;
+
+            cost = cost.saturating_add(
+                grid.get_movement_cost(previous, *index),
+            );
+            length += horizontal.hypot(vertical);
+
+            previous = *index;
+        }
+
+        PathResult {
+            path,
+            cost,
+            length,
+            expanded_amount,
+            generated_amount,
+            peak_open_amount,
+            closed_list,
+        }
+    }
+
+    /// Getter of the path.
+    ///
+    /// # Returns:
+    ///
+    /// The indices of the path nodes, departure excluded.
+    pub fn get_path(&self) -> &[usize] {
+        &self.path
+    }
+
+    /// Consumes the result and returns the path.
+    ///
+    /// # Returns:
+    ///
+    /// The indices of the path nodes, departure excluded.
+    pub fn into_path(self) -> Vec<usize> {
+        self.path
+    }
+
+    /// Getter of the cost.
+    ///
+    /// # Returns:
+    ///
+    /// The sum of the movement costs of the grid along the path,
+    /// terrain weights included, whatever the research algorithm is
+    /// (saturated at `u32::MAX`).
+    pub fn get_cost(&self) -> u32 {
+        self.cost
+    }
+
+    /// Getter of the length.
+    ///
+    /// # Returns:
+    ///
+    /// The euclidean length of the path from the departure,
+    /// the distance between two neighbour nodes being 1.
+    pub fn get_length(&self) -> f64 {
+        self.length
+    }
+
+    /// Returns the amount of nodes expanded by the research:
+    /// the nodes whose children have been added to the open list.
+    ///
+    /// # Returns:
+    ///
+    /// The amount of expanded nodes, both sides included
+    /// for the bidirectional research.
+    pub fn get_expanded_amount(&self) -> usize {
+        self.expanded_amount
+    }
+
+    /// Returns the amount of nodes generated by the research:
+    /// the nodes added to the open list, once each.
+    ///
+    /// # Returns:
+    ///
+    /// The amount of generated nodes, both sides included
+    /// for the bidirectional research.
+    pub fn get_generated_amount(&self) -> usize {
+        self.generated_amount
+    }
+
+    /// Returns the largest amount of nodes the open list
+    /// has contained at once during the research.
+    ///
+    /// # Returns:
+    ///
+    /// The peak size of the open list, the sum of the peaks
+    /// of both sides for the bidirectional research.
+    pub fn get_peak_open_amount(&self) -> usize {
+        self.peak_open_amount
+    }
+
+    /// Getter of the closed list.
+    ///
+    /// # Returns:
+    ///
+    /// The closed nodes sorted in ascending order, both sides included
+    /// for the bidirectional research, or None if it has not been kept.
+    pub fn get_closed_list(&self) -> Option<&[usize]> {
+        self.closed_list.as_deref()
+    }
+}
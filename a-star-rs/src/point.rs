@@ -0,0 +1,32 @@
+//! Horizontal and vertical coordinates of a node.
+
+/// Position of a node on the grid: `x` is the column from the left side,
+/// `y` is the line from the top side.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+
+    /// Constructor for a new Point object.
+    ///
+    /// # Arguments:
+    ///
+    /// * `x` - the column of the node
+    /// * `y` - the line of the node
+    ///
+    /// # Returns:
+    ///
+    /// Created Point object.
+    pub fn new(
+        x: usize,
+        y: usize,
+    ) -> Point {
+        Point {
+            x,
+            y,
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Research run a few expansions at a time, so many researches
+//! can share the time of the frames.
+
+use grid::Grid;
+use path_error::PathError;
+use search_algorithm::SearchAlgorithm;
+use search_context::SearchContext;
+
+/// State of a research after one step.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Progress {
+
+    /// The research is not over, the next step continues it.
+    Running,
+
+    /// The path has been found: the indices of its nodes,
+    /// departure excluded.
+    Found(Vec<usize>),
+
+    /// There is no path, for the given reason.
+    NoPath(PathError),
+}
+
+/// Research between two nodes that expands a limited amount of nodes
+/// per step, and keeps its open list and its closed list
+/// between the steps.
+///
+/// The grid must not be modified until the research is over:
+/// after a change, the research must be restarted. The steps
+/// and the restarts only accept grids of the dimensions
+/// of the first one.
+#[derive(Clone)]
+pub struct ResumableSearch {
+    search: SearchContext,
+    width: usize,
+    height: usize,
+    departure_index: usize,
+    arrival_index: usize,
+    progress: Progress,
+}
+
+impl ResumableSearch {
+
+    /// Constructor for a new ResumableSearch object,
+    /// the research is started but no node is expanded yet
+    /// (except the departure and the arrival for the bidirectional
+    /// research).
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid to research the path on
+    /// * `departure` - the departure cell index
+    /// * `arrival` - the arrival cell index
+    /// * `algorithm` - the research algorithm, the jump point research
+    ///   jumps node by node
+    ///
+    /// # Returns:
+    ///
+    /// Created ResumableSearch object.
+    pub fn new(
+        grid: &Grid,
+        departure: usize,
+        arrival: usize,
+        algorithm: SearchAlgorithm,
+    ) -> ResumableSearch {
+
+        let mut search = SearchContext::new();
+        search.set_algorithm(algorithm);
+
+        let mut resumable = ResumableSearch {
+            search,
+            width: grid.get_width(),
+            height: grid.get_height(),
+            departure_index: departure,
+            arrival_index: arrival,
+            progress: Progress::Running,
+        };
+
+        resumable.restart(
+            grid,
+            departure,
+            arrival,
+        );
+
+        resumable
+    }
+
+    /// Expands nodes until the path is found, the arrival is known
+    /// to be unreachable, or the given amount of nodes has been expanded.
+    /// Once the research is over, its result is returned again.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid the research has been started on
+    /// * `max_expansions` - the largest amount of nodes to expand
+    ///
+    /// # Returns:
+    ///
+    /// The progress of the research, or `PathError::OutOfBounds`
+    /// without any expansion if the grid does not have the dimensions
+    /// of the research.
+    pub fn step(
+        &mut self,
+        grid: &Grid,
+        max_expansions: usize,
+    ) -> Progress {
+
+        if !self.has_dimensions_of(grid) {
+            return Progress::NoPath(PathError::OutOfBounds);
+        }
+
+        if self.progress == Progress::Running {
+            self.progress = self.search.resume_research(
+                grid,
+                max_expansions,
+            );
+        }
+
+        self.progress.clone()
+    }
+
+    /// Stops the research, the next steps return
+    /// `Progress::NoPath(PathError::Cancelled)`.
+    /// The memory of the research is kept for a restart.
+    pub fn cancel(&mut self) {
+        self.progress = Progress::NoPath(PathError::Cancelled);
+    }
+
+    /// Starts the research again, between the same nodes or new ones,
+    /// whatever the progress of the previous one is.
+    /// The memory of the previous research is reused.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid to research the path on, the progress
+    ///   becomes `PathError::OutOfBounds` if it does not have
+    ///   the dimensions of the research
+    /// * `departure` - the departure cell index
+    /// * `arrival` - the arrival cell index
+    pub fn restart(
+        &mut self,
+        grid: &Grid,
+        departure: usize,
+        arrival: usize,
+    ) {
+
+        self.departure_index = departure;
+        self.arrival_index = arrival;
+
+        if !self.has_dimensions_of(grid) {
+            self.progress = Progress::NoPath(PathError::OutOfBounds);
+            return;
+        }
+
+        self.progress = match self.search.start_research(
+            grid,
+            departure,
+            arrival,
+        ) {
+            Ok(()) => Progress::Running,
+            Err(error) => Progress::NoPath(error),
+        };
+    }
+
+    /// Getter of the progress.
+    ///
+    /// # Returns:
+    ///
+    /// The progress after the last step.
+    pub fn get_progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    /// Getter of the departure index.
+    ///
+    /// # Returns:
+    ///
+    /// The departure cell index.
+    pub fn get_departure_index(&self) -> usize {
+        self.departure_index
+    }
+
+    /// Getter of the arrival index.
+    ///
+    /// # Returns:
+    ///
+    /// The arrival cell index.
+    pub fn get_arrival_index(&self) -> usize {
+        self.arrival_index
+    }
+
+    /// Getter of the research memory, to read the costs, the parents
+    /// or the statistics of the research (see
+    /// `SearchContext::get_path_result`).
+    ///
+    /// # Returns:
+    ///
+    /// The costs, parents, open list and closed list of the research.
+    pub fn get_search_context(&self) -> &SearchContext {
+        &self.search
+    }
+
+    /// Indicates if a grid has the dimensions of the research.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid given to the research
+    ///
+    /// # Returns:
+    ///
+    /// True if the grid has the same width and height
+    /// as the grid the research has been created on.
+    fn has_dimensions_of(
+        &self,
+        grid: &Grid,
+    ) -> bool {
+        self.width == grid.get_width() && self.height == grid.get_height()
+    }
+}
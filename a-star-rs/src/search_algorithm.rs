@@ -0,0 +1,93 @@
+//! Algorithms that can run the path research.
+
+/// Order in which the research explores the nodes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SearchAlgorithm {
+
+    /// Explores the nodes by sum of cost and heuristic,
+    /// returns the cheapest path if the heuristic is admissible.
+    #[default]
+    AStar,
+
+    /// Explores the nodes by cost without any heuristic,
+    /// returns the cheapest path.
+    Dijkstra,
+
+    /// Explores the nodes by amount of movements, ignoring the costs
+    /// and the terrain weights: returns the path with the fewest nodes.
+    BreadthFirst,
+
+    /// Explores the nodes by heuristic only: usually the fastest research,
+    /// but the returned path is not guaranteed to be the cheapest one.
+    GreedyBestFirst,
+
+    /// Jump Point Search: explores like A* but jumps along the lines
+    /// and only adds to the open list the nodes where the paths can turn.
+    /// Returns a path as cheap as the A* one, on grids with uniform
+    /// terrain weights and eight directions (see
+    /// `jump_point::is_grid_supported`). Only used by the complete
+    /// and the resumable researches, the step by step methods
+    /// explore like A*.
+    JumpPoint,
+
+    /// Explores like A* from the departure and from the arrival at once,
+    /// expanding the side with the smallest open list, until no path
+    /// can be cheaper than the best one going through a node reached
+    /// by both sides. Returns the cheapest path if the heuristic
+    /// is consistent. Only used by the complete and the resumable
+    /// researches, the step by step methods explore like A*.
+    Bidirectional,
+}
+
+impl SearchAlgorithm {
+
+    /// Indicates if the algorithm estimates the cost to the arrival.
+    ///
+    /// # Returns:
+    ///
+    /// True if the heuristic of the grid is used.
+    pub fn uses_heuristic(&self) -> bool {
+        match *self {
+            SearchAlgorithm::AStar |
+            SearchAlgorithm::GreedyBestFirst |
+            SearchAlgorithm::JumpPoint |
+            SearchAlgorithm::Bidirectional => true,
+            SearchAlgorithm::Dijkstra | SearchAlgorithm::BreadthFirst => false,
+        }
+    }
+
+    /// Indicates if the cost of an open node is lowered
+    /// when a cheaper path to it is found.
+    ///
+    /// # Returns:
+    ///
+    /// True if the open nodes are updated.
+    pub fn updates_open_nodes(&self) -> bool {
+        *self != SearchAlgorithm::GreedyBestFirst
+    }
+
+    /// Returns the score of a node into the open list,
+    /// the node with the smallest score is explored first.
+    ///
+    /// # Arguments:
+    ///
+    /// * `cost` - the cost from the departure to the node
+    /// * `heuristic` - the estimated cost from the node to the arrival
+    ///
+    /// # Returns:
+    ///
+    /// The score of the node, saturated at `u32::MAX`.
+    pub fn get_score(
+        &self,
+        cost: u32,
+        heuristic: u32,
+    ) -> u32 {
+        match *self {
+            SearchAlgorithm::AStar |
+            SearchAlgorithm::JumpPoint |
+            SearchAlgorithm::Bidirectional => cost.saturating_add(heuristic),
+            SearchAlgorithm::Dijkstra | SearchAlgorithm::BreadthFirst => cost,
+            SearchAlgorithm::GreedyBestFirst => heuristic,
+        }
+    }
+}
@@ -0,0 +1,1484 @@
+//! Memory of one research, separated from the grid it runs on.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::mem;
+
+use grid::Grid;
+use jump_point::{
+    self,
+    JumpTable,
+};
+use path_error::PathError;
+use path_result::PathResult;
+use resumable_search::Progress;
+use search_algorithm::SearchAlgorithm;
+
+/// Entry of the open list: the score (the sum of cost and heuristic
+/// for A*), the heuristic to prefer the nodes closer to the arrival
+/// on equal scores, and the node index.
+type OpenListEntry = Reverse<(u32, u32, usize)>;
+
+/// State of one node during a research.
+///
+/// The state is only valid if its generation is the generation
+/// of the research, otherwise it belongs to a previous research
+/// and the node has not been reached yet.
+#[derive(Clone, Copy, Default)]
+struct SearchNode {
+    generation: u32,
+    heuristic: u32,
+    cost: u32,
+    parent: Option<usize>,
+    opened: bool,
+    closed: bool,
+}
+
+/// Costs, parents, open list and closed list of a research.
+///
+/// A context can be used for many researches, on one grid or another:
+/// the allocated memory is kept from one research to the next one,
+/// and the previous research is cleared in constant time
+/// by incrementing the generation of the context.
+///
+/// The bidirectional research keeps a second context for the side
+/// of the arrival.
+#[derive(Clone, Default)]
+pub struct SearchContext {
+    nodes: Vec<SearchNode>,
+    generation: u32,
+    algorithm: SearchAlgorithm,
+    departure_index: usize,
+    current_index: usize,
+    arrival_index: usize,
+    open_list: BinaryHeap<OpenListEntry>,
+    open_amount: usize,
+    children_list: Vec<usize>,
+    expanded_amount: usize,
+    generated_amount: usize,
+    peak_open_amount: usize,
+    reverse: Option<Box<SearchContext>>,
+    best_meeting: Option<(u32, usize)>,
+    meeting_offset: u32,
+    cost_overflow: bool,
+}
+
+impl SearchContext {
+
+    /// Constructor for a new empty SearchContext object.
+    ///
+    /// # Returns:
+    ///
+    /// Created SearchContext object.
+    pub fn new() -> SearchContext {
+        SearchContext::default()
+    }
+
+    /// Sets the algorithm of the next researches, A* by default.
+    ///
+    /// # Arguments:
+    ///
+    /// * `algorithm` - the research algorithm
+    pub fn set_algorithm(
+        &mut self,
+        algorithm: SearchAlgorithm,
+    ) {
+        self.algorithm = algorithm;
+    }
+
+    /// Getter of the research algorithm.
+    ///
+    /// # Returns:
+    ///
+    /// The algorithm of the researches.
+    pub fn get_algorithm(&self) -> SearchAlgorithm {
+        self.algorithm
+    }
+
+    /// Clears the state of the previous research and sets the nodes
+    /// of the next one. The departure is added to the closed list.
+    ///
+    /// The nodes states are not cleared one by one: they belong
+    /// to the previous generation and are ignored from now on.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the next research
+    /// * `departure` - the departure cell index
+    /// * `arrival` - the arrival cell index
+    pub fn prepare_research(
+        &mut self,
+        grid: &Grid,
+        departure: usize,
+        arrival: usize,
+    ) {
+
+        let nodes_amount = grid.get_nodes_amount();
+
+        /* the states of the added nodes have the generation 0,
+           so they are ignored like the other ones */
+        self.nodes.resize(nodes_amount, SearchNode::default());
+
+        if self.generation == u32::MAX {
+            for node in self.nodes.iter_mut() {
+                node.generation = 0;
+            }
+            self.generation = 0;
+        }
+
+        self.generation += 1;
+        self.departure_index = departure;
+        self.current_index = departure;
+        self.arrival_index = arrival;
+        self.open_list.clear();
+        self.open_amount = 0;
+        self.children_list.clear();
+        self.expanded_amount = 0;
+        self.generated_amount = 0;
+        self.peak_open_amount = 0;
+        self.cost_overflow = false;
+
+        if let Some(ref mut reverse) = self.reverse {
+            reverse.expanded_amount = 0;
+            reverse.generated_amount = 0;
+            reverse.peak_open_amount = 0;
+        }
+
+        if departure < nodes_amount {
+            self.get_node_mut(departure).closed = true;
+        }
+    }
+
+    /// Returns the state of one node during the current research.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The state of the node, the default one if not reached yet.
+    fn get_node(
+        &self,
+        index: usize,
+    ) -> SearchNode {
+
+        let node = self.nodes[index];
+
+        if node.generation != self.generation {
+            return SearchNode::default();
+        }
+
+        node
+    }
+
+    /// Returns the state of one node during the current research
+    /// for write access, the state of a previous research is cleared.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The state of the node.
+    fn get_node_mut(
+        &mut self,
+        index: usize,
+    ) -> &mut SearchNode {
+
+        let generation = self.generation;
+        let node = &mut self.nodes[index];
+
+        if node.generation != generation {
+            *node = SearchNode {
+                generation,
+                ..SearchNode::default()
+            };
+        }
+
+        node
+    }
+
+    /// Main research method
+    ///
+    /// Runs the research algorithm (A* by default) from the departure node
+    /// until the arrival node is taken from the open list, then rebuilds
+    /// the path by following the parents back to the departure.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid to research the path on
+    /// * `departure` - the departure cell index
+    /// * `arrival` - the arrival cell index
+    ///
+    /// # Returns:
+    ///
+    /// The indices of the path nodes, departure excluded,
+    /// or the reason why there is no path.
+    pub fn research_path(
+        &mut self,
+        grid: &Grid,
+        departure: usize,
+        arrival: usize,
+    ) -> Result<Vec<usize>, PathError> {
+
+        self.start_research(
+            grid,
+            departure,
+            arrival,
+        )?;
+
+        complete_research(|| self.resume_research(grid, usize::MAX))
+    }
+
+    /// Starts a research that is then run by `resume_research`,
+    /// a few expansions at a time.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid to research the path on
+    /// * `departure` - the departure cell index
+    /// * `arrival` - the arrival cell index
+    ///
+    /// # Returns:
+    ///
+    /// The reason why there is no path, if it is already known.
+    pub fn start_research(
+        &mut self,
+        grid: &Grid,
+        departure: usize,
+        arrival: usize,
+    ) -> Result<(), PathError> {
+
+        match self.algorithm {
+            SearchAlgorithm::Bidirectional => self.start_bidirectional(
+                grid,
+                departure,
+                arrival,
+            ),
+            SearchAlgorithm::JumpPoint => self.start_jump_points(
+                grid,
+                None,
+                departure,
+                arrival,
+            ),
+            _ => {
+
+                check_research(grid, departure, arrival)?;
+
+                self.prepare_research(
+                    grid,
+                    departure,
+                    arrival,
+                );
+
+                self.get_node_mut(departure).heuristic =
+                    self.estimate_heuristic(grid, departure);
+
+                self.generate_children_list(grid);
+
+                Ok(())
+            },
+        }
+    }
+
+    /// Runs a research started by `start_research` until the path
+    /// is found, the arrival is known to be unreachable,
+    /// or the given amount of nodes has been expanded.
+    ///
+    /// The grid must not be modified between the start
+    /// and the end of the research, the research must be started
+    /// again otherwise.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid the research has been started on
+    /// * `max_expansions` - the largest amount of nodes to expand
+    ///   before returning
+    ///
+    /// # Returns:
+    ///
+    /// The path if it has been found, the reason why there is no path,
+    /// or `Progress::Running` if the research is not over.
+    pub fn resume_research(
+        &mut self,
+        grid: &Grid,
+        max_expansions: usize,
+    ) -> Progress {
+
+        match self.algorithm {
+            SearchAlgorithm::Bidirectional => self.resume_bidirectional(
+                grid,
+                max_expansions,
+            ),
+            SearchAlgorithm::JumpPoint => self.resume_jump_points(
+                grid,
+                None,
+                max_expansions,
+            ),
+            _ => {
+
+                for _ in 0..max_expansions {
+
+                    self.update_open_list(grid);
+                    self.generate_costs(grid);
+
+                    if self.cost_overflow {
+                        return Progress::NoPath(PathError::CostOverflow);
+                    }
+
+                    if self.open_amount == 0 {
+                        return Progress::NoPath(PathError::Unreachable);
+                    }
+
+                    let final_index = self.iterate();
+
+                    self.generate_children_list(grid);
+
+                    if final_index.is_some() {
+                        return Progress::Found(self.get_path(grid));
+                    }
+                }
+
+                Progress::Running
+            },
+        }
+    }
+
+    /// Researches a path with the jump point research and precomputed
+    /// jumps (JPS+), whatever the algorithm of the context is.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid to research the path on
+    /// * `table` - the jumps generated from the current state of the grid
+    /// * `departure` - the departure cell index
+    /// * `arrival` - the arrival cell index
+    ///
+    /// # Returns:
+    ///
+    /// The indices of the path nodes, departure excluded,
+    /// or the reason why there is no path (`PathError::UnsupportedGrid`
+    /// if the table comes from another state of the grid).
+    pub fn research_path_with_jump_table(
+        &mut self,
+        grid: &Grid,
+        table: &JumpTable,
+        departure: usize,
+        arrival: usize,
+    ) -> Result<Vec<usize>, PathError> {
+
+        self.start_jump_points(
+            grid,
+            Some(table),
+            departure,
+            arrival,
+        )?;
+
+        complete_research(|| {
+            self.resume_jump_points(grid, Some(table), usize::MAX)
+        })
+    }
+
+    /// Starts the jump point research: like A*, but the children
+    /// of a node are the jump points reached from it.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid to research the path on
+    /// * `table` - the precomputed jumps, None to jump node by node
+    /// * `departure` - the departure cell index
+    /// * `arrival` - the arrival cell index
+    ///
+    /// # Returns:
+    ///
+    /// The reason why there is no path, if it is already known.
+    fn start_jump_points(
+        &mut self,
+        grid: &Grid,
+        table: Option<&JumpTable>,
+        departure: usize,
+        arrival: usize,
+    ) -> Result<(), PathError> {
+
+        check_research(grid, departure, arrival)?;
+
+        let supported = match table {
+            Some(table) => table.matches(grid),
+            None => jump_point::is_grid_supported(grid),
+        };
+
+        if !supported {
+            return Err(PathError::UnsupportedGrid);
+        }
+
+        self.prepare_research(
+            grid,
+            departure,
+            arrival,
+        );
+
+        self.get_node_mut(departure).heuristic = grid.estimate_heuristic(
+            departure,
+            arrival,
+        );
+
+        Ok(())
+    }
+
+    /// Runs the jump point research for the given amount of expansions
+    /// at most (see `resume_research`).
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid the research has been started on
+    /// * `table` - the precomputed jumps, None to jump node by node
+    /// * `max_expansions` - the largest amount of nodes to expand
+    ///
+    /// # Returns:
+    ///
+    /// The progress of the research.
+    fn resume_jump_points(
+        &mut self,
+        grid: &Grid,
+        table: Option<&JumpTable>,
+        max_expansions: usize,
+    ) -> Progress {
+
+        let arrival = self.arrival_index;
+        let mut jumps = Vec::new();
+
+        for _ in 0..max_expansions {
+
+            let current_index = self.current_index;
+            let current = self.get_node(current_index);
+
+            match table {
+                Some(table) => table.generate_jumps(
+                    grid,
+                    current_index,
+                    current.parent,
+                    arrival,
+                    &mut jumps,
+                ),
+                None => jump_point::generate_jumps(
+                    grid,
+                    current_index,
+                    current.parent,
+                    arrival,
+                    &mut jumps,
+                ),
+            }
+
+            self.expanded_amount += 1;
+
+            for jump in jumps.iter() {
+
+                let state = self.get_node(jump.index);
+
+                if state.closed {
+                    continue;
+                }
+
+                /* the terrain weights are uniform, so every movement
+                   of the jump has the cost of the first one */
+                let first_index = (
+                    current_index as isize +
+                    (jump.index as isize - current_index as isize) /
+                    jump.steps as isize
+                ) as usize;
+
+                let cost = jump.steps
+                    .checked_mul(grid.get_movement_cost(current_index, first_index))
+                    .and_then(|jump_cost| add_cost(current.cost, jump_cost));
+
+                let cost = match cost {
+                    Some(cost) => cost,
+                    None => return Progress::NoPath(PathError::CostOverflow),
+                };
+
+                if state.opened && state.cost <= cost {
+                    continue;
+                }
+
+                let heuristic = if state.opened {
+                    state.heuristic
+                } else {
+                    self.count_generated_node();
+                    grid.estimate_heuristic(jump.index, arrival)
+                };
+
+                let node = self.get_node_mut(jump.index);
+                node.opened = true;
+                node.heuristic = heuristic;
+                node.cost = cost;
+                node.parent = Some(current_index);
+
+                self.open_list.push(Reverse((
+                    cost.saturating_add(heuristic),
+                    heuristic,
+                    jump.index,
+                )));
+            }
+
+            if self.open_amount == 0 {
+                return Progress::NoPath(PathError::Unreachable);
+            }
+
+            if self.iterate().is_some() {
+                return Progress::Found(self.get_path(grid));
+            }
+        }
+
+        Progress::Running
+    }
+
+    /// Starts the bidirectional research: one A* research from
+    /// the departure and one from the arrival, with the movements
+    /// followed backward, until the best path through a node reached
+    /// by both sides cannot be improved. The departure and the arrival
+    /// are expanded.
+    ///
+    /// Both sides use the average of the two heuristics, so a node
+    /// has the same estimated path cost from both sides and the two
+    /// sides meet in the middle: the score of a node is twice its cost,
+    /// plus the heuristic to the end of its side, minus the heuristic
+    /// to the start of its side (plus the heuristic between
+    /// the departure and the arrival, to stay positive).
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid to research the path on
+    /// * `departure` - the departure cell index
+    /// * `arrival` - the arrival cell index
+    ///
+    /// # Returns:
+    ///
+    /// The reason why there is no path, if it is already known.
+    fn start_bidirectional(
+        &mut self,
+        grid: &Grid,
+        departure: usize,
+        arrival: usize,
+    ) -> Result<(), PathError> {
+
+        check_research(grid, departure, arrival)?;
+
+        let mut reverse = self.reverse
+            .take()
+            .unwrap_or_default();
+
+        reverse.set_algorithm(SearchAlgorithm::AStar);
+
+        self.prepare_research(grid, departure, arrival);
+        reverse.prepare_research(grid, arrival, departure);
+
+        let mut best = None;
+        let offset = self.estimate_heuristic(grid, departure);
+
+        self.get_node_mut(departure).heuristic = offset;
+        reverse.get_node_mut(arrival).heuristic = offset;
+
+        self.expand_current_node(grid, false, &reverse, offset, &mut best);
+        reverse.expand_current_node(grid, true, self, offset, &mut best);
+
+        let cost_overflow = self.cost_overflow || reverse.cost_overflow;
+
+        self.best_meeting = best;
+        self.meeting_offset = offset;
+        self.reverse = Some(reverse);
+
+        if cost_overflow {
+            return Err(PathError::CostOverflow);
+        }
+
+        Ok(())
+    }
+
+    /// Runs the bidirectional research for the given amount
+    /// of expansions at most (see `resume_research`).
+    ///
+    /// When the sides meet, the parents and the costs of the arrival
+    /// side of the path are copied into this context, so the path,
+    /// the costs and the parents read like after a research
+    /// from the departure.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid the research has been started on
+    /// * `max_expansions` - the largest amount of nodes to expand
+    ///
+    /// # Returns:
+    ///
+    /// The progress of the research.
+    fn resume_bidirectional(
+        &mut self,
+        grid: &Grid,
+        max_expansions: usize,
+    ) -> Progress {
+
+        let mut reverse = self.reverse
+            .take()
+            .unwrap_or_default();
+
+        let meeting = self.meet_reverse_research(
+            grid,
+            &mut reverse,
+            max_expansions,
+        );
+
+        let progress = match meeting {
+            Some(Ok(meeting)) => {
+
+                let mut index = meeting;
+
+                while index != self.arrival_index {
+
+                    let next = reverse.get_node(index)
+                        .parent
+                        .expect("every reached node has a parent");
+
+                    let cost = self.get_node(index).cost +
+                        grid.get_movement_cost(index, next);
+
+                    let node = self.get_node_mut(next);
+                    node.cost = cost;
+                    node.parent = Some(index);
+
+                    index = next;
+                }
+
+                Progress::Found(self.get_path(grid))
+            },
+            Some(Err(error)) => Progress::NoPath(error),
+            None => Progress::Running,
+        };
+
+        self.reverse = Some(reverse);
+
+        progress
+    }
+
+    /// Expands the side of the departure and the side of the arrival
+    /// until the cheapest path is known, or until the given amount
+    /// of nodes has been expanded.
+    ///
+    /// The sum of the smallest scores of both sides is a lower bound
+    /// of twice the cost of the paths that are not known yet,
+    /// so the research stops when this sum reaches twice the cost
+    /// of the best known path.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    /// * `reverse` - the started research from the arrival
+    /// * `max_expansions` - the largest amount of nodes to expand
+    ///
+    /// # Returns:
+    ///
+    /// The node where the two sides of the cheapest path meet,
+    /// or the reason why there is no path,
+    /// or None if the research is not over.
+    fn meet_reverse_research(
+        &mut self,
+        grid: &Grid,
+        reverse: &mut SearchContext,
+        max_expansions: usize,
+    ) -> Option<Result<usize, PathError>> {
+
+        let mut best = self.best_meeting;
+        let offset = self.meeting_offset;
+
+        for _ in 0..max_expansions {
+
+            let forward_score = self.get_smallest_score();
+            let reverse_score = reverse.get_smallest_score();
+
+            let bound = match (forward_score, reverse_score) {
+                (Some(forward), Some(reverse)) =>
+                    Some(forward as u64 + reverse as u64),
+                _ => None,
+            };
+
+            /* when one side has explored every reachable node,
+               the best known path cannot be improved */
+            match (best, bound) {
+                (Some((cost, meeting)), Some(bound))
+                    if bound >= 2 * (cost as u64 + offset as u64) =>
+                    return Some(Ok(meeting)),
+                (Some((_, meeting)), None) => return Some(Ok(meeting)),
+                (None, None) => return Some(Err(PathError::Unreachable)),
+                _ => {},
+            }
+
+            if self.open_amount <= reverse.open_amount {
+                self.iterate();
+                self.expand_current_node(grid, false, reverse, offset, &mut best);
+            } else {
+                reverse.iterate();
+                reverse.expand_current_node(grid, true, self, offset, &mut best);
+            }
+
+            self.best_meeting = best;
+
+            if self.cost_overflow || reverse.cost_overflow {
+                return Some(Err(PathError::CostOverflow));
+            }
+        }
+
+        None
+    }
+
+    /// Adds the children of the current node to the open list
+    /// of one side of the bidirectional research, and updates
+    /// the best known path when a child has been reached by both sides.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    /// * `reversed` - true for the side of the arrival,
+    ///   whose movements go from the children to the current node
+    /// * `other` - the research of the other side
+    /// * `offset` - the heuristic between the departure and the arrival
+    /// * `best` - the cost of the best known path and its meeting node
+    fn expand_current_node(
+        &mut self,
+        grid: &Grid,
+        reversed: bool,
+        other: &SearchContext,
+        offset: u32,
+        best: &mut Option<(u32, usize)>,
+    ) {
+
+        let current_index = self.current_index;
+        let current_cost = self.get_node(current_index).cost;
+
+        self.generate_children_list(grid);
+        self.expanded_amount += 1;
+
+        for position in 0..self.children_list.len() {
+
+            let index = self.children_list[position];
+
+            if !grid.get_node(index).is_usuable() {
+                continue;
+            }
+
+            let state = self.get_node(index);
+
+            if state.closed {
+                continue;
+            }
+
+            let cost = add_cost(current_cost, if reversed {
+                grid.get_movement_cost(index, current_index)
+            } else {
+                grid.get_movement_cost(current_index, index)
+            });
+
+            let cost = match cost {
+                Some(cost) => cost,
+                None => {
+                    self.cost_overflow = true;
+                    continue;
+                },
+            };
+
+            if state.opened && state.cost <= cost {
+                continue;
+            }
+
+            let heuristic = if state.opened {
+                state.heuristic
+            } else {
+                self.count_generated_node();
+                self.estimate_heuristic(grid, index)
+            };
+
+            let node = self.get_node_mut(index);
+            node.opened = true;
+            node.heuristic = heuristic;
+            node.cost = cost;
+            node.parent = Some(current_index);
+
+            let start_heuristic = grid.estimate_heuristic(
+                index,
+                self.departure_index,
+            );
+
+            let score = (2 * cost as u64 + heuristic as u64 + offset as u64)
+                .saturating_sub(start_heuristic as u64);
+
+            self.open_list.push(Reverse((
+                score.min(u32::MAX as u64) as u32,
+                heuristic,
+                index,
+            )));
+
+            let other_state = other.get_node(index);
+
+            if !other_state.opened && !other_state.closed {
+                continue;
+            }
+
+            let path_cost = match add_cost(cost, other_state.cost) {
+                Some(path_cost) => path_cost,
+                None => {
+                    self.cost_overflow = true;
+                    continue;
+                },
+            };
+
+            match *best {
+                Some((best_cost, _)) if best_cost <= path_cost => {},
+                _ => *best = Some((path_cost, index)),
+            }
+        }
+    }
+
+    /// Returns the smallest score of the open list, the outdated entries
+    /// of the nodes already closed are removed first.
+    ///
+    /// # Returns:
+    ///
+    /// The smallest score, or None if the open list is empty.
+    fn get_smallest_score(&mut self) -> Option<u32> {
+
+        loop {
+
+            let (score, index) = match self.open_list.peek() {
+                Some(&Reverse((score, _, index))) => (score, index),
+                None => return None,
+            };
+
+            if !self.get_node(index).closed {
+                return Some(score);
+            }
+
+            self.open_list.pop();
+        }
+    }
+
+    /// Returns the amount of nodes expanded by the last research
+    /// from the side of the departure: the nodes whose children
+    /// have been added to the open list.
+    ///
+    /// # Returns:
+    ///
+    /// The amount of expanded nodes.
+    pub fn get_departure_side_expansions(&self) -> usize {
+        self.expanded_amount
+    }
+
+    /// Returns the amount of nodes expanded by the last research
+    /// from the side of the arrival.
+    ///
+    /// # Returns:
+    ///
+    /// The amount of expanded nodes, 0 if the research
+    /// is not bidirectional.
+    pub fn get_arrival_side_expansions(&self) -> usize {
+        match self.reverse {
+            Some(ref reverse) => reverse.expanded_amount,
+            None => 0,
+        }
+    }
+
+    /// Adds one node reached for the first time to the amount
+    /// of nodes into the open list, and to the research statistics.
+    fn count_generated_node(&mut self) {
+
+        self.open_amount += 1;
+        self.generated_amount += 1;
+
+        if self.open_amount > self.peak_open_amount {
+            self.peak_open_amount = self.open_amount;
+        }
+    }
+
+    /// Researches the path like `research_path` and returns it
+    /// with its cost, its length and the statistics of the research.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid to research the path on
+    /// * `departure` - the departure cell index
+    /// * `arrival` - the arrival cell index
+    /// * `keep_closed_list` - true to return the closed list,
+    ///   for debugging
+    ///
+    /// # Returns:
+    ///
+    /// The path and its research statistics,
+    /// or the reason why there is no path.
+    pub fn research_path_result(
+        &mut self,
+        grid: &Grid,
+        departure: usize,
+        arrival: usize,
+        keep_closed_list: bool,
+    ) -> Result<PathResult, PathError> {
+
+        let path = self.research_path(
+            grid,
+            departure,
+            arrival,
+        )?;
+
+        Ok(self.get_path_result(
+            grid,
+            path,
+            keep_closed_list,
+        ))
+    }
+
+    /// Returns a path found by the last research with its cost,
+    /// its length and the statistics of the research.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the last research
+    /// * `path` - the path found by the last research
+    /// * `keep_closed_list` - true to return the closed list,
+    ///   for debugging
+    ///
+    /// # Returns:
+    ///
+    /// The path and its research statistics.
+    pub fn get_path_result(
+        &self,
+        grid: &Grid,
+        path: Vec<usize>,
+        keep_closed_list: bool,
+    ) -> PathResult {
+
+        let mut expanded_amount = self.expanded_amount;
+        let mut generated_amount = self.generated_amount;
+        let mut peak_open_amount = self.peak_open_amount;
+        let mut closed_list = None;
+
+        if keep_closed_list {
+            closed_list = Some(self.get_closed_list());
+        }
+
+        /* the side of the arrival is kept after a bidirectional research,
+           but its amounts are cleared by the other researches */
+        if let Some(ref reverse) = self.reverse {
+
+            if reverse.expanded_amount > 0 {
+                expanded_amount += reverse.expanded_amount;
+                generated_amount += reverse.generated_amount;
+                peak_open_amount += reverse.peak_open_amount;
+
+                if let Some(ref mut closed_list) = closed_list {
+                    closed_list.extend(reverse.get_closed_list());
+                    closed_list.sort_unstable();
+                    closed_list.dedup();
+                }
+            }
+        }
+
+        PathResult::new(
+            grid,
+            self.departure_index,
+            path,
+            expanded_amount,
+            generated_amount,
+            peak_open_amount,
+            closed_list,
+        )
+    }
+
+    /// Rebuilds the path by following the parents from the arrival
+    /// back to the departure. The nodes between a parent and a node
+    /// on the same line, column or diagonal (a jump) are added.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    ///
+    /// # Returns:
+    ///
+    /// The indices of the path nodes, departure excluded.
+    fn get_path(
+        &self,
+        grid: &Grid,
+    ) -> Vec<usize> {
+
+        let width = grid.get_width() as isize;
+        let mut path = Vec::new();
+        let mut current_index = self.arrival_index;
+
+        while current_index != self.departure_index {
+
+            let parent = self.get_node(current_index)
+                .parent
+                .expect("every reached node has a parent");
+
+            let step =
+                (parent as isize / width - current_index as isize / width)
+                    .signum() * width +
+                (parent as isize % width - current_index as isize % width)
+                    .signum();
+
+            while current_index != parent {
+                path.push(current_index);
+                current_index = (current_index as isize + step) as usize;
+            }
+        }
+
+        path.reverse();
+
+        path
+    }
+
+    /// Generate the heuristics of every node from departure and arrival.
+    ///
+    /// The research only generates the heuristic of a node
+    /// when this one is reached for the first time.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    pub fn generate_heuristics(
+        &mut self,
+        grid: &Grid,
+    ) {
+
+        for index in 0..self.nodes.len() {
+            self.get_node_mut(index).heuristic = grid.estimate_heuristic(
+                index,
+                self.arrival_index,
+            );
+        }
+    }
+
+    /// Estimates the cost from one node to the arrival node
+    /// if the research algorithm uses a heuristic.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The estimated cost, 0 if the algorithm has no heuristic.
+    fn estimate_heuristic(
+        &self,
+        grid: &Grid,
+        index: usize,
+    ) -> u32 {
+
+        if !self.algorithm.uses_heuristic() {
+            return 0;
+        }
+
+        grid.estimate_heuristic(
+            index,
+            self.arrival_index,
+        )
+    }
+
+    /// Returns the cost of the movement from one node to one of its children
+    /// for the research algorithm.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    /// * `from` - the index of the node
+    /// * `to` - the index of the child
+    ///
+    /// # Returns:
+    ///
+    /// The movement cost of the grid, 1 for a breadth first research.
+    fn get_movement_cost(
+        &self,
+        grid: &Grid,
+        from: usize,
+        to: usize,
+    ) -> u32 {
+
+        if self.algorithm == SearchAlgorithm::BreadthFirst {
+            return 1;
+        }
+
+        grid.get_movement_cost(from, to)
+    }
+
+    /// Generates the list of children for the current index.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    pub fn generate_children_list(
+        &mut self,
+        grid: &Grid,
+    ) {
+
+        let mut children = mem::take(&mut self.children_list);
+
+        grid.generate_children_list(
+            self.current_index,
+            &mut children,
+        );
+
+        self.children_list = children;
+    }
+
+    /// Returns the children open list.
+    ///
+    /// # Returns:
+    ///
+    /// Vector that contains the indices of all the open list nodes,
+    /// sorted in ascending order.
+    pub fn get_open_list(&self) -> Vec<usize> {
+        (0..self.nodes.len())
+            .filter(|index| {
+                let node = self.get_node(*index);
+                node.opened && !node.closed
+            })
+            .collect()
+    }
+
+    /// Generates the costs of the children that are in the open list.
+    ///
+    /// The cost of a movement is multiplied by the terrain weight
+    /// of the child. A breadth first research counts the movements instead.
+    ///
+    /// A child reached for the first time takes the current node
+    /// as parent. A child already in the open list is updated
+    /// only if going through the current node is cheaper
+    /// (never for a greedy best first research).
+    /// Every new or lowered cost is pushed into the open list.
+    /// A cost that does not fit into a `u32` stops the research
+    /// with `PathError::CostOverflow`.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    pub fn generate_costs(
+        &mut self,
+        grid: &Grid,
+    ) {
+
+        let current_index = self.current_index;
+        let current_cost = self.get_node(current_index).cost;
+        let algorithm = self.algorithm;
+
+        self.expanded_amount += 1;
+
+        for position in 0..self.children_list.len() {
+
+            let index = self.children_list[position];
+
+            let state = self.get_node(index);
+
+            if !state.opened || state.closed {
+                continue;
+            }
+
+            let reached = state.parent.is_some();
+
+            if reached && !algorithm.updates_open_nodes() {
+                continue;
+            }
+
+            let cost = add_cost(
+                current_cost,
+                self.get_movement_cost(grid, current_index, index),
+            );
+
+            let cost = match cost {
+                Some(cost) => cost,
+                None => {
+                    self.cost_overflow = true;
+                    continue;
+                },
+            };
+
+            if reached && state.cost <= cost {
+                continue;
+            }
+
+            let heuristic = if reached {
+                state.heuristic
+            } else {
+                self.estimate_heuristic(grid, index)
+            };
+
+            let node = self.get_node_mut(index);
+            node.heuristic = heuristic;
+            node.cost = cost;
+            node.parent = Some(current_index);
+
+            self.open_list.push(Reverse((
+                algorithm.get_score(cost, heuristic),
+                heuristic,
+                index,
+            )));
+        }
+    }
+
+    /// Getter for the closed list.
+    ///
+    /// # Returns:
+    ///
+    /// Vector containing the closed list, sorted in ascending order.
+    pub fn get_closed_list(&self) -> Vec<usize> {
+        (0..self.nodes.len())
+            .filter(|index| self.get_node(*index).closed)
+            .collect()
+    }
+
+    /// Iterates to the next node and remove the target from the open list.
+    ///
+    /// The open list node with the smallest sum of cost and heuristic
+    /// becomes the current node and is moved into the closed list.
+    /// On equal sums, the node with the smallest heuristic is chosen.
+    ///
+    /// Returns:
+    ///
+    /// The index of the arrival node if it is the new current node,
+    /// or None (also when the open list is empty)
+    pub fn iterate(&mut self) -> Option<usize> {
+
+        while let Some(Reverse((_, _, target))) = self.open_list.pop() {
+
+            /* outdated entry of a node whose cost has been lowered since */
+            if self.get_node(target).closed {
+                continue;
+            }
+
+            self.current_index = target;
+            self.get_node_mut(target).closed = true;
+            self.open_amount -= 1;
+
+            if target == self.arrival_index {
+                return Some(target);
+            }
+
+            return None;
+        }
+
+        None
+    }
+
+    /// Getter for the current index.
+    ///
+    /// # Returns:
+    ///
+    /// The current index.
+    pub fn get_current_index(&self) -> usize {
+        self.current_index
+    }
+
+    /// Setter of the current index.
+    ///
+    /// # Arguments:
+    ///
+    /// `index` - the current index
+    pub fn set_current_index(
+        &mut self,
+        current: usize,
+    ) {
+        self.current_index = current;
+    }
+
+    /// Sets the open list as the children list.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    pub fn update_open_list(
+        &mut self,
+        grid: &Grid,
+    ) {
+
+        for position in 0..self.children_list.len() {
+
+            let index = self.children_list[position];
+
+            if !grid.get_node(index).is_usuable() {
+                continue;
+            }
+
+            let node = self.get_node_mut(index);
+
+            if node.opened || node.closed {
+                continue;
+            }
+
+            node.opened = true;
+            self.count_generated_node();
+        }
+    }
+
+    /// Returns the children list.
+    ///
+    /// # Returns:
+    ///
+    /// List of the current children.
+    pub fn get_children_list(&self) -> Vec<usize> {
+        self.children_list.clone()
+    }
+
+    /// Returns the heuristic of one node.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The estimated cost to the arrival, 0 if not generated yet.
+    pub fn get_heuristic(
+        &self,
+        index: usize,
+    ) -> u32 {
+        self.get_node(index).heuristic
+    }
+
+    /// Sets the cost of one node.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    /// * `cost` - the cost to set
+    pub fn set_cost(
+        &mut self,
+        index: usize,
+        cost: u32,
+    ) {
+        self.get_node_mut(index).cost = cost;
+    }
+
+    /// Returns the cost of one node.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The cost of the cheapest known path from the departure to the node.
+    pub fn get_cost(
+        &self,
+        index: usize,
+    ) -> u32 {
+        self.get_node(index).cost
+    }
+
+    /// Sets the parent of one node.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    /// * `parent` - the index of the node the cheapest known path comes from
+    pub fn set_parent(
+        &mut self,
+        index: usize,
+        parent: usize,
+    ) {
+        self.get_node_mut(index).parent = Some(parent);
+    }
+
+    /// Returns the parent of one node.
+    ///
+    /// # Arguments:
+    ///
+    /// * `index` - the index of the node
+    ///
+    /// # Returns:
+    ///
+    /// The index of the previous node on the cheapest known path,
+    /// or None if the node has not been reached yet.
+    pub fn get_parent(
+        &self,
+        index: usize,
+    ) -> Option<usize> {
+        self.get_node(index).parent
+    }
+}
+
+/// Checks that a path can be researched between two nodes.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid of the research
+/// * `departure` - the departure cell index
+/// * `arrival` - the arrival cell index
+///
+/// # Returns:
+///
+/// The reason why there is no path, if any.
+fn check_research(
+    grid: &Grid,
+    departure: usize,
+    arrival: usize,
+) -> Result<(), PathError> {
+
+    let nodes_amount = grid.get_nodes_amount();
+
+    if departure >= nodes_amount || arrival >= nodes_amount {
+        return Err(PathError::OutOfBounds);
+    }
+
+    if !grid.get_node(departure).is_usuable() {
+        return Err(PathError::DepartureBlocked);
+    }
+
+    if !grid.get_node(arrival).is_usuable() {
+        return Err(PathError::ArrivalBlocked);
+    }
+
+    if departure == arrival {
+        return Err(PathError::DepartureIsArrival);
+    }
+
+    Ok(())
+}
+
+/// Adds a movement cost to the cost of a node.
+///
+/// # Arguments:
+///
+/// * `cost` - the cost of the node
+/// * `movement_cost` - the cost of the movement
+///
+/// # Returns:
+///
+/// The sum, or None if it does not fit into a cost: `u32::MAX` is
+/// an overflow too, as the grid saturates the movement costs to it.
+fn add_cost(
+    cost: u32,
+    movement_cost: u32,
+) -> Option<u32> {
+    cost.checked_add(movement_cost)
+        .filter(|sum| *sum != u32::MAX)
+}
+
+/// Resumes a research until it is over.
+///
+/// # Arguments:
+///
+/// * `resume` - resumes the research for many expansions
+///
+/// # Returns:
+///
+/// The indices of the path nodes, departure excluded,
+/// or the reason why there is no path.
+fn complete_research<F>(mut resume: F) -> Result<Vec<usize>, PathError>
+where
+    F: FnMut() -> Progress,
+{
+
+    loop {
+        match resume() {
+            Progress::Running => {},
+            Progress::Found(path) => return Ok(path),
+            Progress::NoPath(error) => return Err(error),
+        }
+    }
+}
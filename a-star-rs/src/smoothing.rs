@@ -0,0 +1,430 @@
+//! Post-processing of the researched paths: waypoints where the path
+//! turns, string pulling and curves.
+
+use grid::Grid;
+
+/// Amount of points checked per node of length, to make sure
+/// a curve does not go through unusuable nodes or forbidden corners.
+const CHECKS_PER_NODE: f64 = 16.0;
+
+/// Curves that can be sampled along the waypoints of a path.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Curve {
+
+    /// Catmull-Rom spline: goes through every waypoint.
+    #[default]
+    CatmullRom,
+
+    /// Quadratic Bezier curves that round the corners: every waypoint
+    /// is the control point of a curve from the middle of the previous
+    /// segment to the middle of the next one.
+    Bezier,
+}
+
+/// Keeps only the nodes of a path where the direction changes.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid of the research
+/// * `departure` - the departure cell index
+/// * `path` - the indices of the path nodes, departure excluded
+///
+/// # Returns:
+///
+/// The nodes where the path turns, departure excluded,
+/// the last one being the arrival.
+pub fn compress_path(
+    grid: &Grid,
+    departure: usize,
+    path: &[usize],
+) -> Vec<usize> {
+
+    let width = grid.get_width() as i64;
+    let get_direction = |from: usize, to: usize| (
+        to as i64 % width - from as i64 % width,
+        to as i64 / width - from as i64 / width,
+    );
+
+    let mut waypoints = Vec::new();
+    let mut previous = departure;
+
+    for (position, index) in path.iter().enumerate() {
+
+        let is_turn = match path.get(position + 1) {
+            Some(next) =>
+                get_direction(previous, *index) != get_direction(*index, *next),
+            None => true,
+        };
+
+        if is_turn {
+            waypoints.push(*index);
+        }
+
+        previous = *index;
+    }
+
+    waypoints
+}
+
+/// Removes the waypoints that can be skipped: every waypoint is followed
+/// by the furthest one in line of sight (see `Grid::has_line_of_sight`).
+///
+/// # Arguments:
+///
+/// * `grid` - the grid of the research
+/// * `departure` - the departure cell index
+/// * `path` - the indices of the path nodes or of its waypoints,
+///   departure excluded
+///
+/// # Returns:
+///
+/// The remaining waypoints, departure excluded,
+/// the last one being the arrival.
+pub fn pull_string(
+    grid: &Grid,
+    departure: usize,
+    path: &[usize],
+) -> Vec<usize> {
+
+    let mut waypoints = Vec::new();
+    let mut anchor = departure;
+    let mut position = 0;
+
+    while position < path.len() {
+
+        /* the next waypoint is always reachable from the anchor */
+        let furthest = (position + 1..path.len())
+            .rev()
+            .find(|next| grid.has_line_of_sight(anchor, path[*next]) == Ok(true))
+            .unwrap_or(position);
+
+        anchor = path[furthest];
+        waypoints.push(anchor);
+        position = furthest + 1;
+    }
+
+    waypoints
+}
+
+/// Samples a curve along the waypoints of a path. The parts of the curve
+/// that would go through an unusuable node or outside of the grid
+/// are replaced by the straight segments between the waypoints.
+///
+/// # Arguments:
+///
+/// * `grid` - the grid of the research
+/// * `departure` - the departure cell index
+/// * `waypoints` - the waypoints of the path, departure excluded,
+///   joined by segments that only go through usuable nodes
+/// * `curve` - the kind of curve
+/// * `samples_per_segment` - the amount of points per segment
+///   between two waypoints, at least 1
+///
+/// # Returns:
+///
+/// The points of the curve from the centre of the departure
+/// to the centre of the arrival, as horizontal and vertical positions
+/// in nodes (the centre of the node (x, y) being (x + 0.5, y + 0.5)).
+pub fn sample_curve(
+    grid: &Grid,
+    departure: usize,
+    waypoints: &[usize],
+    curve: Curve,
+    samples_per_segment: usize,
+) -> Vec<(f64, f64)> {
+
+    let samples_per_segment = samples_per_segment.max(1);
+    let width = grid.get_width();
+
+    let centres: Vec<(f64, f64)> = Some(&departure)
+        .into_iter()
+        .chain(waypoints.iter())
+        .map(|index| (
+            (index % width) as f64 + 0.5,
+            (index / width) as f64 + 0.5,
+        ))
+        .collect();
+
+    let mut points = vec![centres[0]];
+
+    if waypoints.is_empty() {
+        return points;
+    }
+
+    match curve {
+        Curve::CatmullRom => {
+
+            for segment in 1..centres.len() {
+
+                let first = centres[segment - 1];
+                let second = centres[segment];
+                let before = centres[segment.saturating_sub(2)];
+                let after = centres[(segment + 1).min(centres.len() - 1)];
+
+                let spline = |ratio: f64| (
+                    get_catmull_rom(before.0, first.0, second.0, after.0, ratio),
+                    get_catmull_rom(before.1, first.1, second.1, after.1, ratio),
+                );
+
+                add_samples(
+                    grid,
+                    &mut points,
+                    spline,
+                    get_distance(first, second),
+                    samples_per_segment,
+                    &[second],
+                );
+            }
+        },
+        Curve::Bezier => {
+
+            for corner in 1..centres.len() - 1 {
+
+                let start = get_middle(centres[corner - 1], centres[corner]);
+                let control = centres[corner];
+                let end = get_middle(centres[corner], centres[corner + 1]);
+
+                let bezier = |ratio: f64| (
+                    get_bezier(start.0, control.0, end.0, ratio),
+                    get_bezier(start.1, control.1, end.1, ratio),
+                );
+
+                /* the straight half segment before the corner */
+                if points.last() != Some(&start) {
+                    points.push(start);
+                }
+
+                add_samples(
+                    grid,
+                    &mut points,
+                    bezier,
+                    get_distance(start, control) + get_distance(control, end),
+                    samples_per_segment,
+                    &[control, end],
+                );
+            }
+
+            points.push(centres[centres.len() - 1]);
+        },
+    }
+
+    points
+}
+
+/// Adds the samples of one part of a curve, from its start excluded
+/// to its end included, or the fallback points if the part or one
+/// of the straight chords between its samples goes through
+/// an unusuable node or through a corner the movement model
+/// does not allow to cross (see `Grid::has_line_of_sight`).
+///
+/// # Arguments:
+///
+/// * `grid` - the grid of the research
+/// * `points` - the points the samples are added to
+/// * `part` - the position along the part, for a ratio from 0 to 1
+/// * `length` - the approximate length of the part
+/// * `samples` - the amount of samples of the part
+/// * `fallback` - the points added instead of the samples
+fn add_samples<F>(
+    grid: &Grid,
+    points: &mut Vec<(f64, f64)>,
+    part: F,
+    length: f64,
+    samples: usize,
+    fallback: &[(f64, f64)],
+)
+where
+    F: Fn(f64) -> (f64, f64),
+{
+
+    let checks = (length * CHECKS_PER_NODE).ceil() as usize + 1;
+    let mut previous = None;
+
+    let is_clear = (0..=checks).all(|check| {
+
+        let (x, y) = part(check as f64 / checks as f64);
+
+        if
+            x < 0.0 ||
+            y < 0.0 ||
+            x as usize >= grid.get_width() ||
+            y as usize >= grid.get_height()
+        {
+            return false;
+        }
+
+        let index = y as usize * grid.get_width() + x as usize;
+
+        /* going from one node to another one follows the movement model,
+           a diagonal change goes through a corner */
+        let is_clear = match previous {
+            Some(previous) if previous != index =>
+                grid.has_line_of_sight(previous, index) == Ok(true),
+            _ => grid.get_node(index).is_usuable(),
+        };
+
+        previous = Some(index);
+        is_clear
+    });
+
+    let samples: Vec<(f64, f64)> = (1..=samples)
+        .map(|sample| part(sample as f64 / samples as f64))
+        .collect();
+
+    /* the points are joined by straight chords, not by the curve */
+    let mut chord_start = points[points.len() - 1];
+
+    let is_clear = is_clear && samples.iter().all(|sample| {
+
+        let is_chord_clear = has_chord_line_of_sight(
+            grid,
+            chord_start,
+            *sample,
+        );
+
+        chord_start = *sample;
+        is_chord_clear
+    });
+
+    if !is_clear {
+        points.extend(fallback.iter().cloned());
+        return;
+    }
+
+    points.extend(samples);
+}
+
+/// Indicates if the chord between two points of a curve only goes
+/// through usuable nodes, from the node of the first point
+/// to the node of the second one (see `Grid::has_line_of_sight`).
+///
+/// # Arguments:
+///
+/// * `grid` - the grid of the research
+/// * `from` - the first point
+/// * `to` - the second point
+///
+/// # Returns:
+///
+/// True if both points are on the grid and their nodes
+/// are in line of sight.
+fn has_chord_line_of_sight(
+    grid: &Grid,
+    from: (f64, f64),
+    to: (f64, f64),
+) -> bool {
+
+    let get_index = |(x, y): (f64, f64)| {
+
+        if
+            x < 0.0 ||
+            y < 0.0 ||
+            x as usize >= grid.get_width() ||
+            y as usize >= grid.get_height()
+        {
+            return None;
+        }
+
+        Some(y as usize * grid.get_width() + x as usize)
+    };
+
+    match (get_index(from), get_index(to)) {
+        (Some(from), Some(to)) => grid.has_line_of_sight(from, to) == Ok(true),
+        _ => false,
+    }
+}
+
+/// Returns one coordinate of a uniform Catmull-Rom spline
+/// between its second and its third control points.
+///
+/// # Arguments:
+///
+/// * `before` - the coordinate of the point before the segment
+/// * `first` - the coordinate of the start of the segment
+/// * `second` - the coordinate of the end of the segment
+/// * `after` - the coordinate of the point after the segment
+/// * `ratio` - the position along the segment, from 0 to 1
+///
+/// # Returns:
+///
+/// The coordinate of the spline.
+fn get_catmull_rom(
+    before: f64,
+    first: f64,
+    second: f64,
+    after: f64,
+    ratio: f64,
+) -> f64 {
+
+    let square = ratio * ratio;
+
+    0.5 * (
+        2.0 * first +
+        (second - before) * ratio +
+        (2.0 * before - 5.0 * first + 4.0 * second - after) * square +
+        (3.0 * first - before - 3.0 * second + after) * square * ratio
+    )
+}
+
+/// Returns one coordinate of a quadratic Bezier curve.
+///
+/// # Arguments:
+///
+/// * `start` - the coordinate of the start of the curve
+/// * `control` - the coordinate of the control point
+/// * `end` - the coordinate of the end of the curve
+/// * `ratio` - the position along the curve, from 0 to 1
+///
+/// # Returns:
+///
+/// The coordinate of the curve.
+fn get_bezier(
+    start: f64,
+    control: f64,
+    end: f64,
+    ratio: f64,
+) -> f64 {
+
+    let remaining = 1.0 - ratio;
+
+    remaining * remaining * start +
+        2.0 * remaining * ratio * control +
+        ratio * ratio * end
+}
+
+/// Returns the middle of two points.
+///
+/// # Arguments:
+///
+/// * `first` - the first point
+/// * `second` - the second point
+///
+/// # Returns:
+///
+/// The point halfway between them.
+fn get_middle(
+    first: (f64, f64),
+    second: (f64, f64),
+) -> (f64, f64) {
+    (
+        (first.0 + second.0) / 2.0,
+        (first.1 + second.1) / 2.0,
+    )
+}
+
+/// Returns the distance between two points.
+///
+/// # Arguments:
+///
+/// * `first` - the first point
+/// * `second` - the second point
+///
+/// # Returns:
+///
+/// The euclidean distance.
+fn get_distance(
+    first: (f64, f64),
+    second: (f64, f64),
+) -> f64 {
+    (second.0 - first.0).hypot(second.1 - first.1)
+}
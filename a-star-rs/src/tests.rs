@@ -1,13 +1,93 @@
+#![allow(clippy::module_inception)]
+
 #[cfg(test)]
 mod tests {
 
+    use std::ptr;
+    use std::sync::Arc;
+    use std::thread;
+
+    use any_angle::{
+        research_any_angle_path,
+        AnyAngleAlgorithm,
+    };
+    use grid::Grid;
+    use line_of_sight::{
+        bresenham_line,
+        supercover_line,
+    };
+    use hierarchical::HierarchicalGrid;
+    use ffi::{
+        astar_find_path,
+        astar_find_path_with_costs,
+        astar_get_position,
+        astar_grid_find_path,
+        astar_grid_flow_field,
+        astar_grid_free,
+        astar_grid_find_path_result,
+        astar_grid_new,
+        astar_grid_set_algorithm,
+        astar_grid_set_blocked,
+        astar_grid_set_cost,
+        astar_grid_set_move_costs,
+        astar_grid_set_movement,
+        AstarPathResult,
+        Position,
+        ASTAR_ERROR_BUFFER_TOO_SMALL,
+        ASTAR_ERROR_COST_OVERFLOW,
+        ASTAR_ERROR_NULL_POINTER,
+        ASTAR_ERROR_OUT_OF_BOUNDS,
+        ASTAR_ERROR_UNREACHABLE,
+        ASTAR_DIRECTION_LEFT,
+        ASTAR_DIRECTION_NONE,
+        ASTAR_ALGORITHM_BREADTH_FIRST,
+        ASTAR_MOVEMENT_EIGHT_DIRECTIONS,
+        ASTAR_MOVEMENT_FOUR_DIRECTIONS,
+        ASTAR_UNREACHABLE_DISTANCE,
+    };
+    use flow_field::{
+        NO_DIRECTION,
+        UNREACHABLE,
+    };
+    use get_path;
+    use get_path_with_costs;
+    use jump_point::{
+        self,
+        JumpTable,
+    };
+    use heuristic::{
+        Chebyshev,
+        Euclidean,
+        Heuristic,
+        Manhattan,
+        Octile,
+        Zero,
+    };
+    use PATH_BUFFER_TOO_SMALL;
+    use PATH_FOUND;
+    use move_costs::MoveCosts;
+    use movement::Movement;
     use nodes::Nodes;
+    use path_error::PathError;
+    use point::Point;
+    use resumable_search::{
+        Progress,
+        ResumableSearch,
+    };
+    use search_algorithm::SearchAlgorithm;
+    use smoothing::{
+        compress_path,
+        pull_string,
+        sample_curve,
+        Curve,
+    };
+    use search_context::SearchContext;
 
     #[test]
     fn test_create_nodes() {
 
-        const WIDTH: u8 = 10;
-        const HEIGHT: u8 = 10;
+        const WIDTH: usize = 10;
+        const HEIGHT: usize = 10;
         const DEPARTURE_INDEX: usize = 0;
         const ARRIVAL_INDEX: usize = 10;
         let mut nodes = Nodes::new(
@@ -29,28 +109,25 @@ mod tests {
 
         nodes.generate_heuristics();
 
-        const FIRST_INDEX_EXPECTED_HEURISTIC: u8 = 1;
+        const FIRST_INDEX_EXPECTED_HEURISTIC: u32 = 10;
         assert_eq!(
-            nodes.get_node_by_index(FIRST_INDEX)
-                .get_heuristic(),
+            nodes.get_heuristic(FIRST_INDEX),
             FIRST_INDEX_EXPECTED_HEURISTIC,
             "Unexpected heuristic.",
         );
 
         const SECOND_INDEX: usize = 2;
-        const SECOND_INDEX_EXPECTED_HEURISTIC: u8 = 2;
+        const SECOND_INDEX_EXPECTED_HEURISTIC: u32 = 24;
         assert_eq!(
-            nodes.get_node_by_index(SECOND_INDEX)
-                .get_heuristic(),
+            nodes.get_heuristic(SECOND_INDEX),
             SECOND_INDEX_EXPECTED_HEURISTIC,
             "Unexpected heuristic.",
         );
 
         const LAST_INDEX: usize = 99;
-        const LAST_INDEX_EXPECTED_HEURISTIC: u8 = 12;
+        const LAST_INDEX_EXPECTED_HEURISTIC: u32 = 122;
         assert_eq!(
-            nodes.get_node_by_index(LAST_INDEX)
-                .get_heuristic(),
+            nodes.get_heuristic(LAST_INDEX),
             LAST_INDEX_EXPECTED_HEURISTIC,
             "Unexpected heuristic.",
         );
@@ -59,8 +136,8 @@ mod tests {
     #[test]
     fn test_generated_children_open_list() {
 
-        const WIDTH: u8 = 10;
-        const HEIGHT: u8 = 10;
+        const WIDTH: usize = 10;
+        const HEIGHT: usize = 10;
         const DEPARTURE_INDEX: usize = 0;
         const ARRIVAL_INDEX: usize = 10;
         let mut nodes = Nodes::new(
@@ -74,7 +151,7 @@ mod tests {
         nodes.set_current_index(FIRST_INDEX);
         nodes.generate_children_list();
         let mut children = nodes.get_children_list();
-        children.sort_by(|a, b| a.cmp(b));
+        children.sort();
 
         assert_eq!(
             children,
@@ -86,7 +163,7 @@ mod tests {
         nodes.set_current_index(SECOND_INDEX);
         nodes.generate_children_list();
         let mut children = nodes.get_children_list();
-        children.sort_by(|a, b| a.cmp(b));
+        children.sort();
 
         assert_eq!(
             children,
@@ -98,7 +175,7 @@ mod tests {
         nodes.set_current_index(THIRD_INDEX);
         nodes.generate_children_list();
         let mut children = nodes.get_children_list();
-        children.sort_by(|a, b| a.cmp(b));
+        children.sort();
 
         assert_eq!(
             children,
@@ -110,7 +187,7 @@ mod tests {
         nodes.set_current_index(FOURTH_INDEX);
         nodes.generate_children_list();
         let mut children = nodes.get_children_list();
-        children.sort_by(|a, b| a.cmp(b));
+        children.sort();
 
         assert_eq!(
             children,
@@ -122,7 +199,7 @@ mod tests {
         nodes.set_current_index(FIFTH_INDEX);
         nodes.generate_children_list();
         let mut children = nodes.get_children_list();
-        children.sort_by(|a, b| a.cmp(b));
+        children.sort();
 
         assert_eq!(
             children,
@@ -134,8 +211,8 @@ mod tests {
     #[test]
     fn test_generated_costs() {
 
-        const WIDTH: u8 = 10;
-        const HEIGHT: u8 = 10;
+        const WIDTH: usize = 10;
+        const HEIGHT: usize = 10;
         const DEPARTURE_INDEX: usize = 0;
         const ARRIVAL_INDEX: usize = 10;
         let mut nodes = Nodes::new(
@@ -150,29 +227,25 @@ mod tests {
         nodes.generate_costs();
 
         assert_eq!(
-            nodes.get_node_by_index(0)
-                .get_cost(),
+            nodes.get_cost(0),
             0,
             "unexpected cost",
         );
 
         assert_eq!(
-            nodes.get_node_by_index(1)
-                .get_cost(),
+            nodes.get_cost(1),
             10,
             "unexpected cost",
         );
 
         assert_eq!(
-            nodes.get_node_by_index(10)
-                .get_cost(),
+            nodes.get_cost(10),
             10,
             "unexpected cost",
         );
 
         assert_eq!(
-            nodes.get_node_by_index(11)
-                .get_cost(),
+            nodes.get_cost(11),
             14,
             "unexpected cost",
         );
@@ -181,8 +254,8 @@ mod tests {
     #[test]
     fn test_iterate_once() {
 
-        const WIDTH: u8 = 10;
-        const HEIGHT: u8 = 10;
+        const WIDTH: usize = 10;
+        const HEIGHT: usize = 10;
         const FIRST_DEPARTURE_INDEX: usize = 0;
         const FIRST_ARRIVAL_INDEX: usize = 25;
         let mut nodes = Nodes::new(
@@ -207,19 +280,19 @@ mod tests {
 
         assert_eq!(
             nodes.get_current_index(),
-            1,
+            11,
             "unexpected current",
         );
 
         assert_eq!(
             nodes.get_closed_list(),
-            [0, 1],
+            [0, 11],
             "unexpected closed list",
         );
 
         assert_eq!(
             nodes.get_open_list(),
-            [10, 11],
+            [1, 10],
             "unexpected open list",
         );
     }
@@ -227,8 +300,8 @@ mod tests {
     #[test]
     fn test_iterate_twice() {
 
-        const WIDTH: u8 = 10;
-        const HEIGHT: u8 = 10;
+        const WIDTH: usize = 10;
+        const HEIGHT: usize = 10;
         const FIRST_DEPARTURE_INDEX: usize = 0;
         const FIRST_ARRIVAL_INDEX: usize = 25;
         let mut nodes = Nodes::new(
@@ -246,7 +319,7 @@ mod tests {
 
         assert_eq!(
             nodes.get_current_index(),
-            1,
+            11,
             "unexpected current",
         );
 
@@ -257,7 +330,7 @@ mod tests {
 
         assert_eq!(
             nodes.get_current_index(),
-            10,
+            22,
             "unexpected current",
         );
     }
@@ -265,8 +338,8 @@ mod tests {
     #[test]
     fn test_open_list_update() {
 
-        const WIDTH: u8 = 10;
-        const HEIGHT: u8 = 10;
+        const WIDTH: usize = 10;
+        const HEIGHT: usize = 10;
         const FIRST_DEPARTURE_INDEX: usize = 0;
         const FIRST_ARRIVAL_INDEX: usize = 25;
         let mut nodes = Nodes::new(
@@ -302,8 +375,8 @@ mod tests {
 
         // TODO: #58 implement the complete search test
 
-        const WIDTH: u8 = 6;
-        const HEIGHT: u8 = 6;
+        const WIDTH: usize = 6;
+        const HEIGHT: usize = 6;
         const FIRST_DEPARTURE_INDEX: usize = 13;
         const FIRST_ARRIVAL_INDEX: usize = 34;
         let mut nodes = Nodes::new(
@@ -325,7 +398,7 @@ mod tests {
         nodes.generate_costs();
 
         let mut open_list = nodes.get_open_list();
-        open_list.sort_by(|a, b| a.cmp(b));
+        open_list.sort();
 
         assert_eq!(
             open_list,
@@ -340,8 +413,7 @@ mod tests {
         );
 
         assert_eq!(
-            nodes.get_node_by_index(14)
-                .get_cost(),
+            nodes.get_cost(14),
             10,
             "unexpected cost",
         );
@@ -352,60 +424,58 @@ mod tests {
 
         assert_eq!(
             nodes.get_current_index(),
-            14,
+            20,
             "unexpected current",
         );
 
         let mut open_list = nodes.get_open_list();
-        open_list.sort_by(|a, b| a.cmp(b));
+        open_list.sort();
 
         assert_eq!(
             open_list,
-            [6, 7, 8, 12, 18, 19, 20],
+            [6, 7, 8, 12, 14, 18, 19],
             "unexpected open list",
         );
 
         assert_eq!(
             nodes.get_closed_list(),
-            [13, 14],
+            [13, 20],
             "unexpected closed list",
         );
 
         nodes.generate_children_list();
 
         let mut children = nodes.get_children_list();
-        children.sort_by(|a, b| a.cmp(b));
+        children.sort();
 
         assert_eq!(
             children,
-            [7, 8, 9, 13, 15, 19, 20, 21],
+            [13, 14, 15, 19, 21, 25, 26, 27],
             "unexpected children",
         );
 
         nodes.update_open_list();
 
         let mut open_list = nodes.get_open_list();
-        open_list.sort_by(|a, b| a.cmp(b));
+        open_list.sort();
 
         assert_eq!(
             open_list,
-            [6, 7, 8, 9, 12, 18, 19, 20, 21],
+            [6, 7, 8, 12, 14, 18, 19, 21, 26, 27],
             "unexpected open list",
         );
 
         nodes.generate_costs();
 
         assert_eq!(
-            nodes.get_node_by_index(21)
-                .get_cost(),
+            nodes.get_cost(21),
             24,
             "unexpected cost",
         );
 
         assert_eq!(
-            nodes.get_node_by_index(9)
-                .get_cost(),
-            24,
+            nodes.get_cost(27),
+            28,
             "unexpected cost",
         );
 
@@ -413,44 +483,44 @@ mod tests {
 
         assert_eq!(
             nodes.get_current_index(),
-            19,
+            27,
             "unexpected current",
         );
 
         nodes.generate_children_list();
 
         let mut children = nodes.get_children_list();
-        children.sort_by(|a, b| a.cmp(b));
+        children.sort();
 
         assert_eq!(
             children,
-            [12, 13, 14, 18, 20, 24, 25, 26],
+            [20, 21, 22, 26, 28, 32, 33, 34],
             "unexpected children",
         );
 
         nodes.update_open_list();
 
         let mut open_list = nodes.get_open_list();
-        open_list.sort_by(|a, b| a.cmp(b));
+        open_list.sort();
 
         assert_eq!(
             open_list,
-            [6, 7, 8, 9, 12, 18, 20, 21, 24, 26],
+            [6, 7, 8, 12, 14, 18, 19, 21, 22, 26, 28, 32, 33, 34],
             "unexpected open list",
         );
 
         assert_eq!(
             nodes.get_closed_list(),
-            [13, 14, 19],
+            [13, 20, 27],
             "unexpected closed list",
         );
     }
 
     #[test]
-    fn test_backward_movement() {
+    fn test_parents() {
 
-        const WIDTH: u8 = 6;
-        const HEIGHT: u8 = 6;
+        const WIDTH: usize = 6;
+        const HEIGHT: usize = 6;
         const FIRST_DEPARTURE_INDEX: usize = 13;
         const FIRST_ARRIVAL_INDEX: usize = 34;
         let mut nodes = Nodes::new(
@@ -476,98 +546,108 @@ mod tests {
         nodes.generate_heuristics();
         nodes.generate_children_list();
 
-        // perform height iterations
-        for _ in 0..11 {
+        // perform four iterations
+        for _ in 0..4 {
             nodes.update_open_list();
             nodes.generate_costs();
             nodes.iterate();
             nodes.generate_children_list();
-            nodes.generate_backward_movement();
         }
 
         assert_eq!(
-            nodes.get_node_by_index(14)
-                .get_backward_movement(),
-            -1,
-            "unexpected backward movement",
+            nodes.get_parent(13),
+            None,
+            "unexpected parent",
         );
 
         assert_eq!(
-            nodes.get_node_by_index(19)
-                .get_backward_movement(),
-            -6,
-            "unexpected backward movement",
+            nodes.get_parent(14),
+            Some(13),
+            "unexpected parent",
         );
 
         assert_eq!(
-            nodes.get_node_by_index(7)
-                .get_backward_movement(),
-            6,
-            "unexpected backward movement",
+            nodes.get_parent(20),
+            Some(13),
+            "unexpected parent",
         );
 
         assert_eq!(
-            nodes.get_node_by_index(12)
-                .get_backward_movement(),
-            1,
-            "unexpected backward movement",
+            nodes.get_parent(21),
+            Some(20),
+            "unexpected parent",
         );
 
         assert_eq!(
-            nodes.get_node_by_index(20)
-                .get_backward_movement(),
-            -7, // - 6 - 1
-            "unexpected backward movement",
+            nodes.get_parent(28),
+            Some(21),
+            "unexpected parent",
         );
 
         assert_eq!(
-            nodes.get_node_by_index(8)
-                .get_backward_movement(),
-            5, // 6 - 1
-            "unexpected backward movement",
+            nodes.get_parent(26),
+            Some(20),
+            "unexpected parent",
         );
+    }
 
-        assert_eq!(
-            nodes.get_node_by_index(18)
-                .get_backward_movement(),
-            -5, // - 6 + 1
-            "unexpected backward movement",
-        );
+    #[test]
+    fn test_cheaper_parent_replaces_previous_one() {
 
-        assert_eq!(
-            nodes.get_node_by_index(6)
-                .get_backward_movement(),
-            7, // + 6 + 1
-            "unexpected backward movement",
+        const WIDTH: usize = 10;
+        const HEIGHT: usize = 10;
+        const DEPARTURE_INDEX: usize = 0;
+        const ARRIVAL_INDEX: usize = 25;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            HEIGHT,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
         );
 
+        const CURRENT_INDEX: usize = 11;
+        nodes.generate_heuristics();
+        nodes.generate_children_list();
+        nodes.update_open_list();
+        nodes.generate_costs();
+
+        nodes.iterate();
+
         assert_eq!(
-            nodes.get_node_by_index(1)
-                .get_backward_movement(),
-            6,
-            "unexpected backward movement",
+            nodes.get_current_index(),
+            CURRENT_INDEX,
+            "unexpected current",
         );
 
+        // simulates a previous and more expensive route to the node 12
+        const EXPENSIVE_COST: u32 = 50;
+        nodes.get_search_context_mut()
+            .set_cost(12, EXPENSIVE_COST);
+        nodes.get_search_context_mut()
+            .set_parent(12, 1);
+
+        nodes.generate_children_list();
+        nodes.update_open_list();
+        nodes.generate_costs();
+
         assert_eq!(
-            nodes.get_node_by_index(21)
-                .get_backward_movement(),
-            -7, // - 6 - 1
-            "unexpected backward movement",
+            nodes.get_cost(12),
+            24,
+            "unexpected relaxed cost",
         );
 
         assert_eq!(
-            nodes.get_node_by_index(28)
-                .get_backward_movement(),
-            -7, // - 6 - 1
-            "unexpected backward movement",
+            nodes.get_parent(12),
+            Some(CURRENT_INDEX),
+            "unexpected relaxed parent",
         );
     }
 
     #[test]
     fn test_research_api() {
 
-        const WIDTH: u8 = 6;
-        const HEIGHT: u8 = 6;
+        const WIDTH: usize = 6;
+        const HEIGHT: usize = 6;
         const FIRST_DEPARTURE_INDEX: usize = 13;
         const FIRST_ARRIVAL_INDEX: usize = 34;
         let mut nodes = Nodes::new(
@@ -591,15 +671,3981 @@ mod tests {
 
         assert_eq!(
             nodes.research_path(),
-            [14, 21, 28, 34],
+            Ok(vec![20, 21, 28, 34]),
+            "unexpected path",
+        );
+
+        assert_eq!(
+            nodes.get_parent(34),
+            Some(28),
+            "unexpected parent",
+        );
+    }
+
+    #[test]
+    fn test_research_shortest_path_around_wall() {
+
+        const WIDTH: usize = 6;
+        const HEIGHT: usize = 6;
+        const DEPARTURE_INDEX: usize = 0;
+        const ARRIVAL_INDEX: usize = 5;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            HEIGHT,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        for wall in [2, 8, 14, 20, 26].iter() {
+            nodes.get_node_by_index(*wall)
+                .set_unusuable();
+        }
+
+        let path = nodes.research_path()
+            .unwrap();
+
+        assert_eq!(
+            path.last(),
+            Some(&ARRIVAL_INDEX),
+            "unexpected path end",
+        );
+
+        assert!(
+            path.contains(&32),
+            "the path must go around the wall",
+        );
+
+        const SHORTEST_COST: u32 = 120;
+        assert_eq!(
+            nodes.get_cost(ARRIVAL_INDEX),
+            SHORTEST_COST,
+            "unexpected path cost",
+        );
+    }
+
+    #[test]
+    fn test_research_unreachable_arrival() {
+
+        const WIDTH: usize = 6;
+        const HEIGHT: usize = 6;
+        const DEPARTURE_INDEX: usize = 0;
+        const ARRIVAL_INDEX: usize = 35;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            HEIGHT,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        for wall in [28, 29, 34].iter() {
+            nodes.get_node_by_index(*wall)
+                .set_unusuable();
+        }
+
+        assert_eq!(
+            nodes.research_path(),
+            Err(PathError::Unreachable),
+            "unexpected research result",
+        );
+    }
+
+    #[test]
+    fn test_research_invalid_queries() {
+
+        const WIDTH: usize = 6;
+        const HEIGHT: usize = 6;
+        const BLOCKED_INDEX: usize = 7;
+
+        let mut nodes = Nodes::new(WIDTH, HEIGHT, BLOCKED_INDEX, 0);
+        nodes.get_node_by_index(BLOCKED_INDEX)
+            .set_unusuable();
+
+        assert_eq!(
+            nodes.research_path(),
+            Err(PathError::DepartureBlocked),
+            "unexpected research result",
+        );
+
+        let mut nodes = Nodes::new(WIDTH, HEIGHT, 0, BLOCKED_INDEX);
+        nodes.get_node_by_index(BLOCKED_INDEX)
+            .set_unusuable();
+
+        assert_eq!(
+            nodes.research_path(),
+            Err(PathError::ArrivalBlocked),
+            "unexpected research result",
+        );
+
+        let mut nodes = Nodes::new(WIDTH, HEIGHT, 0, 36);
+
+        assert_eq!(
+            nodes.research_path(),
+            Err(PathError::OutOfBounds),
+            "unexpected research result",
+        );
+
+        let mut nodes = Nodes::new(WIDTH, HEIGHT, 3, 3);
+
+        assert_eq!(
+            nodes.research_path(),
+            Err(PathError::DepartureIsArrival),
+            "unexpected research result",
+        );
+    }
+
+    #[test]
+    fn test_get_path_status_codes() {
+
+        let mut path: [u32; 100] = [0; 100];
+        let walls: [u32; 3] = [28, 29, 34];
+
+        assert_eq!(
+            unsafe {
+                get_path(
+                    path.as_mut_ptr(),
+                    path.len(),
+                    walls.as_ptr(),
+                    walls.len(),
+                    ptr::null(),
+                    6,
+                    6,
+                    0,
+                    35,
+                )
+            },
+            1,
+            "unexpected status code",
+        );
+
+        assert_eq!(
+            unsafe {
+                get_path(
+                    path.as_mut_ptr(),
+                    path.len(),
+                    walls.as_ptr(),
+                    0,
+                    ptr::null(),
+                    6,
+                    6,
+                    0,
+                    35,
+                )
+            },
+            PATH_FOUND,
+            "unexpected status code",
+        );
+
+        assert_eq!(
+            path[4],
+            35,
+            "unexpected last path index",
+        );
+
+        assert_eq!(
+            unsafe {
+                get_path(
+                    path.as_mut_ptr(),
+                    4,
+                    walls.as_ptr(),
+                    0,
+                    ptr::null(),
+                    6,
+                    6,
+                    0,
+                    35,
+                )
+            },
+            PATH_BUFFER_TOO_SMALL,
+            "unexpected status code",
+        );
+    }
+
+    #[test]
+    fn test_research_large_grid() {
+
+        const WIDTH: usize = 40;
+        const HEIGHT: usize = 40;
+        const DEPARTURE_INDEX: usize = 0;
+        const ARRIVAL_INDEX: usize = WIDTH * 30 + 20;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            HEIGHT,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        let path = nodes.research_path()
+            .unwrap();
+
+        const DIAGONAL_MOVES: usize = 20;
+        const VERTICAL_MOVES: usize = 10;
+        assert_eq!(
+            path.len(),
+            DIAGONAL_MOVES + VERTICAL_MOVES,
+            "unexpected path length",
+        );
+
+        assert_eq!(
+            nodes.get_cost(ARRIVAL_INDEX),
+            (DIAGONAL_MOVES * 14 + VERTICAL_MOVES * 10) as u32,
+            "unexpected path cost",
+        );
+    }
+
+    #[test]
+    fn test_research_millions_of_nodes() {
+
+        const WIDTH: usize = 2048;
+        const HEIGHT: usize = 2048;
+        const DEPARTURE_INDEX: usize = WIDTH * HEIGHT - 1;
+        const ARRIVAL_INDEX: usize = DEPARTURE_INDEX - 2 * WIDTH - 2;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            HEIGHT,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        assert_eq!(
+            nodes.research_path(),
+            Ok(vec![DEPARTURE_INDEX - WIDTH - 1, ARRIVAL_INDEX]),
             "unexpected path",
         );
+    }
+
+    #[test]
+    fn test_research_cost_overflow() {
+
+        const WIDTH: usize = 8;
+        const STRAIGHT_COST: u32 = u32::MAX / 4;
+        const DIAGONAL_COST: u32 = u32::MAX / 3;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            1,
+            0,
+            WIDTH - 1,
+        );
+
+        /* the fourth movement of the corridor overflows */
+        nodes.set_move_costs(MoveCosts::new(STRAIGHT_COST, DIAGONAL_COST));
+
+        for algorithm in [
+            SearchAlgorithm::AStar,
+            SearchAlgorithm::Dijkstra,
+            SearchAlgorithm::GreedyBestFirst,
+            SearchAlgorithm::JumpPoint,
+            SearchAlgorithm::Bidirectional,
+        ].iter() {
+
+            nodes.set_algorithm(*algorithm);
+
+            assert_eq!(
+                nodes.research_path(),
+                Err(PathError::CostOverflow),
+                "the overflow must be reported",
+            );
+        }
+
+        nodes.set_algorithm(SearchAlgorithm::BreadthFirst);
+
+        assert_eq!(
+            nodes.research_path_result(false)
+                .unwrap()
+                .get_cost(),
+            u32::MAX,
+            "the breadth first research counts the movements",
+        );
+
+        /* the terrain weight saturates the movement cost */
+        nodes.set_algorithm(SearchAlgorithm::AStar);
+        nodes.set_arrival_index(1);
+        nodes.set_weight(1, 255)
+            .unwrap();
+
+        assert_eq!(
+            nodes.research_path(),
+            Err(PathError::CostOverflow),
+            "the overflow must be reported",
+        );
+
+        let field = nodes.generate_flow_field(&[WIDTH - 1])
+            .unwrap();
+
+        assert_eq!(
+            field.get_distance(0),
+            UNREACHABLE,
+            "the distances must saturate",
+        );
+
+        let walls: [u32; 0] = [];
+        let mut path: [u32; WIDTH] = [0; WIDTH];
+
+        unsafe {
+
+            assert_eq!(
+                astar_find_path_with_costs(
+                    path.as_mut_ptr(),
+                    path.len(),
+                    walls.as_ptr(),
+                    walls.len(),
+                    ptr::null(),
+                    WIDTH as u32,
+                    1,
+                    0,
+                    WIDTH as u32 - 1,
+                    STRAIGHT_COST,
+                    DIAGONAL_COST,
+                ),
+                ASTAR_ERROR_COST_OVERFLOW,
+                "unexpected error code",
+            );
+        }
+    }
+
+    #[test]
+    fn test_oversized_grids() {
+
+        /* 2^32 nodes, the last index does not fit into 32 bits */
+        const SIDE: u32 = 1 << 16;
+        let walls: [u32; 0] = [];
+        let mut path: [u32; 4] = [0; 4];
+
+        assert!(
+            astar_grid_new(SIDE, SIDE).is_null(),
+            "the grid must be rejected",
+        );
+
+        assert_eq!(
+            unsafe {
+                astar_find_path(
+                    path.as_mut_ptr(),
+                    path.len(),
+                    walls.as_ptr(),
+                    walls.len(),
+                    ptr::null(),
+                    SIDE,
+                    SIDE,
+                    0,
+                    1,
+                )
+            },
+            ASTAR_ERROR_OUT_OF_BOUNDS,
+            "unexpected error code",
+        );
+    }
+
+    #[test]
+    fn test_heuristics_estimations() {
+
+        const HORIZONTAL_DISTANCE: usize = 3;
+        const VERTICAL_DISTANCE: usize = 4;
+        let costs = MoveCosts::default();
+
+        assert_eq!(
+            Manhattan.estimate(HORIZONTAL_DISTANCE, VERTICAL_DISTANCE, &costs),
+            70,
+            "unexpected manhattan estimation",
+        );
+
+        assert_eq!(
+            Chebyshev.estimate(HORIZONTAL_DISTANCE, VERTICAL_DISTANCE, &costs),
+            40,
+            "unexpected chebyshev estimation",
+        );
+
+        assert_eq!(
+            Octile.estimate(HORIZONTAL_DISTANCE, VERTICAL_DISTANCE, &costs),
+            52,
+            "unexpected octile estimation",
+        );
+
+        assert_eq!(
+            Euclidean.estimate(HORIZONTAL_DISTANCE, VERTICAL_DISTANCE, &costs),
+            49,
+            "unexpected euclidean estimation",
+        );
+
+        assert_eq!(
+            Zero.estimate(HORIZONTAL_DISTANCE, VERTICAL_DISTANCE, &costs),
+            0,
+            "unexpected zero estimation",
+        );
+
+        let closure = |horizontal: usize, vertical: usize, _: &MoveCosts| {
+            (horizontal * vertical) as u32
+        };
+
+        assert_eq!(
+            closure.estimate(HORIZONTAL_DISTANCE, VERTICAL_DISTANCE, &costs),
+            12,
+            "unexpected closure estimation",
+        );
+    }
+
+    #[test]
+    fn test_research_with_heuristics() {
+
+        const WIDTH: usize = 10;
+        const HEIGHT: usize = 10;
+        const DEPARTURE_INDEX: usize = 0;
+        const ARRIVAL_INDEX: usize = 87;
+        const SHORTEST_COST: u32 = 140;
+
+        let mut nodes = Nodes::new(
+            WIDTH,
+            HEIGHT,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        for wall in [40, 41, 42, 43, 44, 45, 46, 47].iter() {
+            nodes.get_node_by_index(*wall)
+                .set_unusuable();
+        }
+
+        let mut dijkstra = nodes.clone();
+        dijkstra.set_heuristic(Zero);
+
+        let mut closure = nodes.clone();
+        closure.set_heuristic(|_: usize, _: usize, _: &MoveCosts| 0);
+
+        let mut euclidean = nodes.clone();
+        euclidean.set_heuristic(Euclidean);
+
+        for grid in [&mut nodes, &mut dijkstra, &mut closure, &mut euclidean]
+            .iter_mut()
+        {
+            grid.research_path()
+                .unwrap();
+
+            assert_eq!(
+                grid.get_cost(ARRIVAL_INDEX),
+                SHORTEST_COST,
+                "unexpected path cost",
+            );
+        }
+    }
+
+    #[test]
+    fn test_octile_with_cheap_diagonals() {
+
+        const WIDTH: usize = 16;
+        const HEIGHT: usize = 12;
+        const RESEARCHES: usize = 40;
+
+        let cheap_diagonals = MoveCosts::new(10, 5);
 
         assert_eq!(
-            nodes.get_node_by_index(34)
-                .get_backward_movement(),
-            -6,
-            "unexpected backward movement",
+            Octile.estimate(3, 4, &cheap_diagonals),
+            20,
+            "a straight move must not cost more than a diagonal one",
         );
+
+        let mut random = get_random_generator(0x0c71);
+
+        for costs in [cheap_diagonals, MoveCosts::new(10, 3)].iter() {
+
+            let mut grid = Grid::new(WIDTH, HEIGHT);
+            let weights = generate_random_weights(
+                &mut random,
+                WIDTH * HEIGHT,
+                20,
+                10,
+                3,
+            );
+
+            grid.set_weights(&weights)
+                .unwrap();
+            grid.set_move_costs(*costs);
+
+            let mut dijkstra = grid.clone();
+            grid.set_heuristic(Octile);
+            dijkstra.set_heuristic(Zero);
+
+            let mut search = SearchContext::new();
+
+            for _ in 0..RESEARCHES {
+
+                let departure = random(WIDTH * HEIGHT);
+                let arrival = random(WIDTH * HEIGHT);
+
+                let expected = search.research_path(&dijkstra, departure, arrival)
+                    .map(|path| get_path_cost(&dijkstra, departure, &path));
+
+                assert_eq!(
+                    search.research_path(&grid, departure, arrival)
+                        .map(|path| get_path_cost(&grid, departure, &path)),
+                    expected,
+                    "the octile research must find the cheapest path",
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_children_with_movements() {
+
+        const WIDTH: usize = 6;
+        const HEIGHT: usize = 6;
+        const CURRENT_INDEX: usize = 14;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            HEIGHT,
+            CURRENT_INDEX,
+            0,
+        );
+
+        /* 8 and 15 surround the diagonal child 9,
+           15 and 20 surround the diagonal child 21 */
+        nodes.get_node_by_index(8)
+            .set_unusuable();
+        nodes.get_node_by_index(15)
+            .set_unusuable();
+        nodes.get_node_by_index(20)
+            .set_unusuable();
+
+        let expected_children = [
+            (Movement::FourDirections, vec![8, 13, 15, 20]),
+            (
+                Movement::EightDirections,
+                vec![7, 8, 9, 13, 15, 19, 20, 21],
+            ),
+            (
+                Movement::EightDirectionsNoCornerCutting,
+                vec![8, 13, 15, 20],
+            ),
+            (
+                Movement::EightDirectionsNoSqueezing,
+                vec![7, 8, 13, 15, 19, 20],
+            ),
+        ];
+
+        for &(movement, ref expected) in expected_children.iter() {
+
+            nodes.set_movement(movement);
+            nodes.set_current_index(CURRENT_INDEX);
+            nodes.generate_children_list();
+
+            let mut children = nodes.get_children_list();
+            children.sort();
+
+            assert_eq!(
+                children,
+                *expected,
+                "unexpected children",
+            );
+        }
+    }
+
+    #[test]
+    fn test_research_with_movements() {
+
+        const WIDTH: usize = 4;
+        const HEIGHT: usize = 4;
+        const DEPARTURE_INDEX: usize = 5;
+        const ARRIVAL_INDEX: usize = 10;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            HEIGHT,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        nodes.get_node_by_index(6)
+            .set_unusuable();
+
+        nodes.set_movement(Movement::EightDirectionsNoSqueezing);
+
+        assert_eq!(
+            nodes.clone().research_path(),
+            Ok(vec![10]),
+            "unexpected path",
+        );
+
+        nodes.set_movement(Movement::EightDirectionsNoCornerCutting);
+
+        assert_eq!(
+            nodes.clone().research_path(),
+            Ok(vec![9, 10]),
+            "unexpected path",
+        );
+
+        nodes.set_movement(Movement::FourDirections);
+
+        let mut four_directions = nodes.clone();
+        four_directions.research_path()
+            .unwrap();
+        four_directions.generate_heuristics();
+
+        four_directions.set_current_index(ARRIVAL_INDEX);
+        four_directions.generate_children_list();
+
+        assert_eq!(
+            four_directions.get_children_list().len(),
+            4,
+            "unexpected children amount",
+        );
+
+        assert_eq!(
+            four_directions.get_cost(ARRIVAL_INDEX),
+            20,
+            "unexpected path cost",
+        );
+
+        assert_eq!(
+            four_directions.get_heuristic(0),
+            40,
+            "manhattan heuristic expected",
+        );
+    }
+
+    #[test]
+    fn test_research_with_weights() {
+
+        const WIDTH: usize = 5;
+        const HEIGHT: usize = 3;
+        const DEPARTURE_INDEX: usize = 5;
+        const ARRIVAL_INDEX: usize = 9;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            HEIGHT,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        assert_eq!(
+            nodes.clone().research_path(),
+            Ok(vec![6, 7, 8, 9]),
+            "unexpected path",
+        );
+
+        const SWAMP_WEIGHT: u8 = 5;
+        for swamp in [6, 7, 8].iter() {
+            nodes.set_weight(*swamp, SWAMP_WEIGHT)
+                .unwrap();
+        }
+
+        assert_eq!(
+            nodes.research_path(),
+            Ok(vec![1, 2, 3, 9]),
+            "unexpected path",
+        );
+
+        assert_eq!(
+            nodes.get_cost(ARRIVAL_INDEX),
+            48,
+            "unexpected path cost",
+        );
+
+        assert_eq!(
+            nodes.set_weight(WIDTH * HEIGHT, SWAMP_WEIGHT),
+            Err(PathError::OutOfBounds),
+            "unexpected weight result",
+        );
+    }
+
+    #[test]
+    fn test_zero_weight_blocks_the_node() {
+
+        let mut grid = Grid::new(4, 1);
+
+        grid.set_weight(2, 3)
+            .unwrap();
+        grid.set_weight(2, 0)
+            .unwrap();
+
+        assert!(
+            !grid.get_node(2).is_usuable(),
+            "a weight of 0 must set the node as unusuable",
+        );
+
+        assert_eq!(
+            grid.get_node(2).get_weight(),
+            3,
+            "the previous weight must be kept",
+        );
+
+        grid.set_weights(&[1, 0, 2, 1])
+            .unwrap();
+
+        assert_eq!(
+            (0..4)
+                .map(|index| grid.get_node(index).is_usuable())
+                .collect::<Vec<bool>>(),
+            vec![true, false, true, true],
+            "a new weight map must replace the previous walls",
+        );
+
+        assert_eq!(
+            grid.get_node(2).get_weight(),
+            2,
+            "unexpected weight",
+        );
+    }
+
+    #[test]
+    fn test_get_path_with_weights() {
+
+        let mut path: [u32; 10] = [0; 10];
+        let walls: [u32; 0] = [];
+        let weights: [u8; 15] = [
+            1, 1, 1, 1, 1,
+            1, 5, 0, 5, 1,
+            1, 1, 1, 1, 1,
+        ];
+
+        assert_eq!(
+            unsafe {
+                get_path(
+                    path.as_mut_ptr(),
+                    path.len(),
+                    walls.as_ptr(),
+                    walls.len(),
+                    weights.as_ptr(),
+                    5,
+                    3,
+                    5,
+                    9,
+                )
+            },
+            PATH_FOUND,
+            "unexpected status code",
+        );
+
+        assert_eq!(
+            path[.. 4],
+            [1, 2, 3, 9],
+            "unexpected path",
+        );
+    }
+
+    #[test]
+    fn test_research_with_move_costs() {
+
+        const WIDTH: usize = 5;
+        const HEIGHT: usize = 5;
+        const DEPARTURE_INDEX: usize = 0;
+        const ARRIVAL_INDEX: usize = 14;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            HEIGHT,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        let mut precise = nodes.clone();
+        precise.set_move_costs(MoveCosts::new(1000, 1414));
+        precise.generate_heuristics();
+
+        assert_eq!(
+            precise.get_heuristic(DEPARTURE_INDEX),
+            4828,
+            "the default heuristic must be scaled to the move costs",
+        );
+
+        let king_moves = MoveCosts::new(1, 1);
+        nodes.set_move_costs(king_moves);
+        nodes.generate_heuristics();
+
+        assert_eq!(
+            nodes.get_move_costs(),
+            king_moves,
+            "unexpected move costs",
+        );
+
+        nodes.research_path()
+            .unwrap();
+
+        assert_eq!(
+            nodes.get_cost(ARRIVAL_INDEX),
+            4,
+            "unexpected path cost",
+        );
+
+        assert_eq!(
+            nodes.get_heuristic(DEPARTURE_INDEX),
+            4,
+            "the default heuristic must be scaled to the move costs",
+        );
+    }
+
+    #[test]
+    fn test_movement_cost_on_narrow_grids() {
+
+        /* 0 1
+           2 3
+           4 5 */
+        let grid = Grid::new(2, 3);
+
+        assert_eq!(
+            (
+                grid.get_movement_cost(0, 1),
+                grid.get_movement_cost(0, 2),
+                grid.get_movement_cost(0, 3),
+                grid.get_movement_cost(1, 2),
+                grid.get_movement_cost(2, 1),
+            ),
+            (10, 10, 14, 14, 14),
+            "the end of a line and the start of the next one are diagonal",
+        );
+
+        let mut search = SearchContext::new();
+        let path = search.research_path(&grid, 1, 4)
+            .unwrap();
+
+        assert_eq!(
+            get_path_cost(&grid, 1, &path),
+            24,
+            "unexpected path cost",
+        );
+
+        let column = Grid::new(1, 3);
+
+        assert_eq!(
+            (
+                column.get_movement_cost(0, 1),
+                column.get_movement_cost(2, 1),
+            ),
+            (10, 10),
+            "the nodes of a column are vertical neighbours",
+        );
+    }
+
+    #[test]
+    fn test_get_path_with_costs() {
+
+        let mut path: [u32; 10] = [0; 10];
+        let walls: [u32; 0] = [];
+
+        /* diagonal movements are more expensive than two straight ones */
+        assert_eq!(
+            unsafe {
+                get_path_with_costs(
+                    path.as_mut_ptr(),
+                    path.len(),
+                    walls.as_ptr(),
+                    walls.len(),
+                    ptr::null(),
+                    3,
+                    3,
+                    0,
+                    4,
+                    1,
+                    3,
+                )
+            },
+            PATH_FOUND,
+            "unexpected status code",
+        );
+
+        assert_eq!(
+            path[1],
+            4,
+            "unexpected path",
+        );
+
+        assert!(
+            path[0] == 1 || path[0] == 3,
+            "unexpected path",
+        );
+    }
+
+    #[test]
+    fn test_research_skips_outdated_open_list_entries() {
+
+        const WIDTH: usize = 8;
+        const HEIGHT: usize = 8;
+        const DEPARTURE_INDEX: usize = 0;
+        const ARRIVAL_INDEX: usize = 63;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            HEIGHT,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        for wall in [9, 18, 27, 36, 45, 54].iter() {
+            nodes.get_node_by_index(*wall)
+                .set_unusuable();
+        }
+
+        let path = nodes.research_path()
+            .unwrap();
+
+        let mut unique_path = path.clone();
+        unique_path.sort();
+        unique_path.dedup();
+
+        assert_eq!(
+            unique_path.len(),
+            path.len(),
+            "a node cannot appear twice into the path",
+        );
+
+        assert_eq!(
+            nodes.get_open_list()
+                .iter()
+                .filter(|index| nodes.get_closed_list().contains(index))
+                .count(),
+            0,
+            "a node cannot be both open and closed",
+        );
+
+        assert_eq!(
+            nodes.get_cost(ARRIVAL_INDEX),
+            104,
+            "unexpected path cost",
+        );
+    }
+
+    #[test]
+    fn test_astar_find_path() {
+
+        let mut path: [u32; 5] = [0; 5];
+        let walls: [u32; 3] = [28, 29, 34];
+
+        assert_eq!(
+            unsafe {
+                astar_find_path(
+                    path.as_mut_ptr(),
+                    path.len(),
+                    walls.as_ptr(),
+                    0,
+                    ptr::null(),
+                    6,
+                    6,
+                    0,
+                    35,
+                )
+            },
+            5,
+            "unexpected written indices amount",
+        );
+
+        assert_eq!(
+            path,
+            [7, 14, 21, 28, 35],
+            "unexpected path",
+        );
+
+        assert_eq!(
+            unsafe {
+                astar_find_path(
+                    path.as_mut_ptr(),
+                    path.len(),
+                    walls.as_ptr(),
+                    walls.len(),
+                    ptr::null(),
+                    6,
+                    6,
+                    0,
+                    35,
+                )
+            },
+            ASTAR_ERROR_UNREACHABLE,
+            "unexpected error code",
+        );
+
+        assert_eq!(
+            unsafe {
+                astar_find_path(
+                    path.as_mut_ptr(),
+                    4,
+                    ptr::null(),
+                    0,
+                    ptr::null(),
+                    6,
+                    6,
+                    0,
+                    35,
+                )
+            },
+            ASTAR_ERROR_BUFFER_TOO_SMALL,
+            "unexpected error code",
+        );
+
+        assert_eq!(
+            unsafe {
+                astar_find_path(
+                    ptr::null_mut(),
+                    5,
+                    ptr::null(),
+                    0,
+                    ptr::null(),
+                    6,
+                    6,
+                    0,
+                    35,
+                )
+            },
+            ASTAR_ERROR_NULL_POINTER,
+            "unexpected error code",
+        );
+    }
+
+    #[test]
+    fn test_astar_get_position() {
+
+        assert_eq!(
+            astar_get_position(5, 13),
+            Position {
+                horizontal: 3,
+                vertical: 2,
+            },
+            "unexpected position",
+        );
+
+        assert_eq!(
+            astar_get_position(0, 13),
+            Position {
+                horizontal: 0,
+                vertical: 0,
+            },
+            "unexpected position",
+        );
+    }
+
+    #[test]
+    fn test_header_declares_every_export() {
+
+        const HEADER: &str = include_str!("../astar.h");
+        const SOURCES: [&str; 1] = [include_str!("ffi.rs")];
+
+        let mut exports = Vec::new();
+
+        for source in SOURCES.iter() {
+
+            let mut lines = source.lines();
+
+            while let Some(line) = lines.next() {
+
+                if line.trim() != "#[no_mangle]" {
+                    continue;
+                }
+
+                let declaration = lines.by_ref()
+                    .find(|line| line.contains("fn "))
+                    .unwrap();
+
+                let name = declaration.split("fn ")
+                    .nth(1)
+                    .unwrap()
+                    .split('(')
+                    .next()
+                    .unwrap();
+
+                exports.push(name.to_string());
+            }
+        }
+
+        assert!(
+            exports.len() >= 11,
+            "exports not found",
+        );
+
+        for export in exports.iter() {
+            assert!(
+                HEADER.contains(&format!("{}(\n", export)),
+                "{} is not declared into astar.h",
+                export,
+            );
+        }
+
+        for line in HEADER.lines() {
+
+            let words: Vec<&str> = line.split_whitespace()
+                .collect();
+
+            if words.len() != 3 || words[0] != "#define" {
+                continue;
+            }
+
+            let constant = format!("pub const {}: ", words[1]);
+            let value = format!(" = {};", words[2]);
+
+            assert!(
+                SOURCES[0].lines()
+                    .any(|line| {
+                        line.starts_with(&constant) && line.ends_with(&value)
+                    }),
+                "{} does not match the Rust constant",
+                words[1],
+            );
+        }
+    }
+
+    #[test]
+    fn test_grid_handle_reused_between_researches() {
+
+        let mut path: [u32; 8] = [0; 8];
+
+        unsafe {
+
+            let grid = astar_grid_new(6, 6);
+
+            for wall in [28, 29, 34].iter() {
+                assert_eq!(
+                    astar_grid_set_blocked(grid, *wall, true),
+                    0,
+                    "unexpected status code",
+                );
+            }
+
+            assert_eq!(
+                astar_grid_find_path(
+                    grid,
+                    0,
+                    35,
+                    path.as_mut_ptr(),
+                    path.len(),
+                ),
+                ASTAR_ERROR_UNREACHABLE,
+                "unexpected error code",
+            );
+
+            /* the door opens, the previous research does not interfere */
+            astar_grid_set_blocked(grid, 34, false);
+
+            assert_eq!(
+                astar_grid_find_path(
+                    grid,
+                    0,
+                    35,
+                    path.as_mut_ptr(),
+                    path.len(),
+                ),
+                6,
+                "unexpected written indices amount",
+            );
+
+            assert_eq!(
+                path[..6],
+                [7, 14, 21, 27, 34, 35],
+                "unexpected path",
+            );
+
+            /* expensive terrain on the diagonal */
+            for index in [7, 14, 21].iter() {
+                astar_grid_set_cost(grid, *index, 9);
+            }
+
+            assert_eq!(
+                astar_grid_find_path(
+                    grid,
+                    35,
+                    0,
+                    path.as_mut_ptr(),
+                    path.len(),
+                ),
+                6,
+                "unexpected written indices amount",
+            );
+
+            assert!(
+                path[..6].iter().all(|index| *index % 7 != 0 || *index == 0),
+                "the expensive diagonal should be avoided",
+            );
+
+            assert_eq!(
+                astar_grid_find_path(
+                    grid,
+                    35,
+                    0,
+                    path.as_mut_ptr(),
+                    5,
+                ),
+                ASTAR_ERROR_BUFFER_TOO_SMALL,
+                "unexpected error code",
+            );
+
+            assert_eq!(
+                astar_grid_set_cost(grid, 36, 2),
+                ASTAR_ERROR_OUT_OF_BOUNDS,
+                "unexpected error code",
+            );
+
+            /* the movement model, the costs and the algorithm
+               are kept between the researches too */
+            let mut get_path_length = |departure: u32, arrival: u32| {
+                astar_grid_find_path(
+                    grid,
+                    departure,
+                    arrival,
+                    path.as_mut_ptr(),
+                    path.len(),
+                )
+            };
+
+            assert_eq!(
+                astar_grid_set_movement(grid, ASTAR_MOVEMENT_FOUR_DIRECTIONS),
+                0,
+                "unexpected status code",
+            );
+
+            assert_eq!(
+                get_path_length(0, 8),
+                3,
+                "the path must not contain diagonal movements",
+            );
+
+            astar_grid_set_movement(grid, ASTAR_MOVEMENT_EIGHT_DIRECTIONS);
+
+            assert_eq!(
+                get_path_length(0, 8),
+                2,
+                "unexpected written indices amount",
+            );
+
+            assert_eq!(
+                astar_grid_set_move_costs(grid, 10, 25),
+                0,
+                "unexpected status code",
+            );
+
+            assert_eq!(
+                get_path_length(0, 8),
+                3,
+                "two straight movements are cheaper than a diagonal one",
+            );
+
+            assert_eq!(
+                astar_grid_set_algorithm(grid, ASTAR_ALGORITHM_BREADTH_FIRST),
+                0,
+                "unexpected status code",
+            );
+
+            assert_eq!(
+                get_path_length(0, 8),
+                2,
+                "the breadth first research ignores the costs",
+            );
+
+            assert_eq!(
+                (
+                    astar_grid_set_movement(grid, 4),
+                    astar_grid_set_algorithm(grid, 6),
+                    astar_grid_set_move_costs(ptr::null_mut(), 10, 14),
+                ),
+                (
+                    ASTAR_ERROR_OUT_OF_BOUNDS,
+                    ASTAR_ERROR_OUT_OF_BOUNDS,
+                    ASTAR_ERROR_NULL_POINTER,
+                ),
+                "unexpected error codes",
+            );
+
+            astar_grid_free(grid);
+
+            assert_eq!(
+                astar_grid_set_blocked(ptr::null_mut(), 0, true),
+                ASTAR_ERROR_NULL_POINTER,
+                "unexpected error code",
+            );
+        }
+    }
+
+    #[test]
+    fn test_astar_grid_find_path_result() {
+
+        let mut path: [u32; 4] = [0; 4];
+        let mut closed: [u32; 4] = [0; 4];
+        let mut result = AstarPathResult::default();
+
+        unsafe {
+
+            let grid = astar_grid_new(4, 1);
+
+            assert_eq!(
+                astar_grid_find_path_result(
+                    grid,
+                    0,
+                    3,
+                    path.as_mut_ptr(),
+                    path.len(),
+                    closed.as_mut_ptr(),
+                    closed.len(),
+                    &mut result,
+                ),
+                3,
+                "unexpected written indices amount",
+            );
+
+            assert_eq!(
+                path[..3],
+                [1, 2, 3],
+                "unexpected path",
+            );
+
+            assert_eq!(
+                closed,
+                [0, 1, 2, 3],
+                "unexpected closed list",
+            );
+
+            assert_eq!(
+                result,
+                AstarPathResult {
+                    cost: 30,
+                    length: 3.0,
+                    expanded_amount: 3,
+                    generated_amount: 3,
+                    peak_open_amount: 1,
+                    closed_amount: 4,
+                },
+                "unexpected path result",
+            );
+
+            /* the closed list is not requested, it is not kept */
+            result = AstarPathResult::default();
+
+            assert_eq!(
+                astar_grid_find_path_result(
+                    grid,
+                    3,
+                    1,
+                    path.as_mut_ptr(),
+                    path.len(),
+                    ptr::null_mut(),
+                    0,
+                    &mut result,
+                ),
+                2,
+                "unexpected written indices amount",
+            );
+
+            assert_eq!(
+                (result.cost, result.closed_amount),
+                (20, 0),
+                "unexpected path result",
+            );
+
+            path = [9; 4];
+
+            assert_eq!(
+                astar_grid_find_path_result(
+                    grid,
+                    0,
+                    3,
+                    path.as_mut_ptr(),
+                    path.len(),
+                    closed.as_mut_ptr(),
+                    3,
+                    &mut result,
+                ),
+                ASTAR_ERROR_BUFFER_TOO_SMALL,
+                "unexpected error code",
+            );
+
+            assert_eq!(
+                path,
+                [9; 4],
+                "nothing must be written if the closed list does not fit",
+            );
+
+            assert_eq!(
+                astar_grid_find_path_result(
+                    grid,
+                    0,
+                    3,
+                    path.as_mut_ptr(),
+                    path.len(),
+                    ptr::null_mut(),
+                    0,
+                    ptr::null_mut(),
+                ),
+                ASTAR_ERROR_NULL_POINTER,
+                "unexpected error code",
+            );
+
+            astar_grid_set_blocked(grid, 2, true);
+
+            assert_eq!(
+                astar_grid_find_path_result(
+                    grid,
+                    0,
+                    3,
+                    path.as_mut_ptr(),
+                    path.len(),
+                    ptr::null_mut(),
+                    0,
+                    &mut result,
+                ),
+                ASTAR_ERROR_UNREACHABLE,
+                "unexpected error code",
+            );
+
+            astar_grid_free(grid);
+        }
+    }
+
+    #[test]
+    fn test_grid_shared_between_threads() {
+
+        const WIDTH: usize = 32;
+        const HEIGHT: usize = 32;
+        let mut grid = Grid::new(WIDTH, HEIGHT);
+
+        /* wall in the middle, open at the bottom */
+        for line in 0..HEIGHT - 1 {
+            grid.get_node_by_index(line * WIDTH + WIDTH / 2)
+                .set_unusuable();
+        }
+
+        let grid = Arc::new(grid);
+        let queries = [(0, 31), (32, 1023), (527, 16), (1023, 0)];
+
+        let mut search = SearchContext::new();
+        let expected: Vec<Result<Vec<usize>, PathError>> = queries.iter()
+            .map(|&(departure, arrival)| {
+                search.research_path(&grid, departure, arrival)
+            })
+            .collect();
+
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let grid = Arc::clone(&grid);
+                thread::spawn(move || {
+                    let mut search = SearchContext::new();
+                    queries.iter()
+                        .map(|&(departure, arrival)| {
+                            search.research_path(&grid, departure, arrival)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            assert_eq!(
+                worker.join().unwrap(),
+                expected,
+                "unexpected paths from a worker thread",
+            );
+        }
+
+        assert_eq!(
+            expected[2],
+            Err(PathError::ArrivalBlocked),
+            "unexpected research result",
+        );
+
+        assert_eq!(
+            expected[0].as_ref().unwrap().last(),
+            Some(&31),
+            "unexpected path",
+        );
+    }
+
+    #[test]
+    fn test_sequential_researches_with_doors() {
+
+        const WIDTH: usize = 6;
+        const HEIGHT: usize = 6;
+        const DOOR_INDEX: usize = 34;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            HEIGHT,
+            0,
+            35,
+        );
+
+        for wall in [28, 29, DOOR_INDEX].iter() {
+            nodes.get_node_by_index(*wall)
+                .set_unusuable();
+        }
+
+        assert_eq!(
+            nodes.research_path(),
+            Err(PathError::Unreachable),
+            "unexpected research result",
+        );
+
+        nodes.set_blocked(DOOR_INDEX, false)
+            .unwrap();
+
+        assert_eq!(
+            nodes.research_path(),
+            Ok(vec![7, 14, 21, 27, 34, 35]),
+            "unexpected research result",
+        );
+
+        nodes.set_departure_index(35);
+        nodes.set_arrival_index(21);
+
+        assert_eq!(
+            nodes.research_path(),
+            Ok(vec![34, 27, 21]),
+            "unexpected research result",
+        );
+
+        nodes.get_node_by_index(DOOR_INDEX)
+            .set_blocked(true);
+
+        assert_eq!(
+            nodes.research_path(),
+            Err(PathError::Unreachable),
+            "unexpected research result",
+        );
+
+        assert_eq!(
+            nodes.set_blocked(WIDTH * HEIGHT, false),
+            Err(PathError::OutOfBounds),
+            "unexpected result",
+        );
+    }
+
+    #[test]
+    fn test_reset_search() {
+
+        let mut nodes = Nodes::new(
+            5,
+            5,
+            0,
+            24,
+        );
+
+        nodes.research_path()
+            .unwrap();
+
+        nodes.set_departure_index(12);
+        nodes.reset_search();
+
+        assert_eq!(
+            nodes.get_closed_list(),
+            [12],
+            "only the departure should be closed",
+        );
+
+        assert_eq!(
+            nodes.get_open_list(),
+            [],
+            "the open list should be empty",
+        );
+
+        assert_eq!(
+            nodes.get_parent(24),
+            None,
+            "the parents should be cleared",
+        );
+
+        assert_eq!(
+            nodes.get_cost(24),
+            0,
+            "the costs should be cleared",
+        );
+
+        assert_eq!(
+            nodes.get_current_index(),
+            12,
+            "unexpected current index",
+        );
+
+        nodes.generate_children_list();
+        nodes.update_open_list();
+        nodes.generate_costs();
+
+        assert_eq!(
+            nodes.iterate(),
+            None,
+            "the arrival cannot be reached in one iteration",
+        );
+
+        assert_eq!(
+            nodes.get_current_index(),
+            18,
+            "unexpected current index",
+        );
+    }
+
+    #[test]
+    fn test_points() {
+
+        let mut nodes = Nodes::from_points(
+            6,
+            4,
+            Point::new(0, 0),
+            Point::new(5, 3),
+        ).unwrap();
+
+        assert_eq!(
+            nodes.get_arrival_index(),
+            23,
+            "unexpected arrival index",
+        );
+
+        assert_eq!(
+            nodes.get_point(14),
+            Ok(Point::new(2, 2)),
+            "unexpected point",
+        );
+
+        assert_eq!(
+            nodes.get_index(Point::new(2, 2)),
+            Ok(14),
+            "unexpected index",
+        );
+
+        assert_eq!(
+            nodes.get_point(24),
+            Err(PathError::OutOfBounds),
+            "the index is outside of the grid",
+        );
+
+        assert_eq!(
+            nodes.get_index(Point::new(6, 0)),
+            Err(PathError::OutOfBounds),
+            "the point is outside of the grid",
+        );
+
+        for y in 0..3 {
+            nodes.node_at(3, y)
+                .unwrap()
+                .set_unusuable();
+        }
+
+        assert!(
+            nodes.node_at(0, 4).is_err(),
+            "the node is outside of the grid",
+        );
+
+        assert_eq!(
+            nodes.research_path_points(),
+            Ok(vec![
+                Point::new(1, 1),
+                Point::new(2, 2),
+                Point::new(3, 3),
+                Point::new(4, 3),
+                Point::new(5, 3),
+            ]),
+            "unexpected path",
+        );
+
+        assert!(
+            Nodes::from_points(
+                6,
+                4,
+                Point::new(0, 0),
+                Point::new(0, 4),
+            ).is_err(),
+            "the arrival is outside of the grid",
+        );
+    }
+
+    #[test]
+    fn test_research_algorithms() {
+
+        const WIDTH: usize = 10;
+        const HEIGHT: usize = 10;
+        const DEPARTURE_INDEX: usize = 0;
+        const ARRIVAL_INDEX: usize = 99;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            HEIGHT,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        /* expensive swamp on the diagonal, wall with a gap at the bottom,
+           so the path has at least 9 movements to the gap and 4 after */
+        for index in [11, 22, 33, 44, 55, 66, 77, 88].iter() {
+            nodes.set_weight(*index, 9)
+                .unwrap();
+        }
+
+        for line in 0..HEIGHT - 1 {
+            nodes.get_node_by_index(line * WIDTH + 5)
+                .set_unusuable();
+        }
+
+        let path_cost = |nodes: &Nodes, path: &[usize]| {
+            let mut previous = DEPARTURE_INDEX;
+            path.iter()
+                .map(|index| {
+                    let cost = nodes.get_movement_cost(previous, *index);
+                    previous = *index;
+                    cost
+                })
+                .sum::<u32>()
+        };
+
+        let mut researches = Vec::new();
+
+        for algorithm in [
+            SearchAlgorithm::AStar,
+            SearchAlgorithm::Dijkstra,
+            SearchAlgorithm::BreadthFirst,
+            SearchAlgorithm::GreedyBestFirst,
+        ].iter() {
+
+            nodes.set_algorithm(*algorithm);
+
+            let path = nodes.research_path()
+                .unwrap();
+
+            assert_eq!(
+                path.last(),
+                Some(&ARRIVAL_INDEX),
+                "the arrival must be reached",
+            );
+
+            researches.push((
+                path_cost(&nodes, &path),
+                path.len(),
+                nodes.get_cost(ARRIVAL_INDEX),
+            ));
+        }
+
+        let (a_star_cost, _, _) = researches[0];
+        let (dijkstra_cost, _, _) = researches[1];
+        let (_, breadth_first_length, breadth_first_cost) = researches[2];
+        let (greedy_cost, _, _) = researches[3];
+
+        assert_eq!(
+            a_star_cost,
+            dijkstra_cost,
+            "A* and Dijkstra must find paths of the same cost",
+        );
+
+        assert_eq!(
+            breadth_first_length,
+            13,
+            "the breadth first path must have the fewest nodes",
+        );
+
+        assert_eq!(
+            breadth_first_cost,
+            13,
+            "the breadth first cost must be the amount of movements",
+        );
+
+        assert!(
+            researches.iter().all(|research| research.1 >= 13),
+            "no path can have fewer nodes than the breadth first one",
+        );
+
+        assert!(
+            greedy_cost >= a_star_cost,
+            "no path can be cheaper than the A* one",
+        );
+
+        nodes.set_algorithm(SearchAlgorithm::Dijkstra);
+        nodes.research_path()
+            .unwrap();
+
+        assert_eq!(
+            nodes.get_heuristic(12),
+            0,
+            "Dijkstra must not estimate the heuristics",
+        );
+    }
+
+    #[test]
+    fn test_flow_field() {
+
+        const WIDTH: usize = 4;
+        const HEIGHT: usize = 3;
+
+        /* 0 1 X 3
+           4 5 X X
+           8 9 A B */
+        let mut nodes = Nodes::new(WIDTH, HEIGHT, 0, 11);
+
+        for wall in [2, 6, 7].iter() {
+            nodes.set_blocked(*wall, true)
+                .unwrap();
+        }
+
+        let field = nodes.generate_flow_field(&[0])
+            .unwrap();
+
+        assert_eq!(
+            field.get_distances().len(),
+            WIDTH * HEIGHT,
+            "one distance per node expected",
+        );
+
+        assert_eq!(
+            field.get_distance(0),
+            0,
+            "the goal distance must be 0",
+        );
+
+        assert_eq!(
+            field.get_direction(0),
+            NO_DIRECTION,
+            "the goal has no direction",
+        );
+
+        assert_eq!(
+            field.get_next_index(0),
+            None,
+            "the goal has no next node",
+        );
+
+        assert_eq!(
+            (field.get_distance(1), field.get_direction(1)),
+            (10, 4),
+            "the node must move to the left",
+        );
+
+        assert_eq!(
+            (field.get_distance(4), field.get_direction(4)),
+            (10, 6),
+            "the node must move to the top",
+        );
+
+        assert_eq!(
+            (field.get_distance(5), field.get_direction(5)),
+            (14, 5),
+            "the node must move to the top left",
+        );
+
+        assert_eq!(
+            field.get_next_index(5),
+            Some(0),
+            "unexpected next node",
+        );
+
+        assert_eq!(
+            (field.get_distance(3), field.get_direction(3)),
+            (UNREACHABLE, NO_DIRECTION),
+            "the enclosed node cannot reach the goal",
+        );
+
+        assert_eq!(
+            field.get_distance(2),
+            UNREACHABLE,
+            "a blocked node cannot reach the goal",
+        );
+
+        /* following the field from any node reaches the goal */
+        let mut index = 11;
+        let mut movements = 0;
+
+        while let Some(next) = field.get_next_index(index) {
+
+            assert!(
+                field.get_distance(next) < field.get_distance(index),
+                "every movement must get closer to the goal",
+            );
+
+            index = next;
+            movements += 1;
+        }
+
+        assert_eq!(
+            index,
+            0,
+            "the field must lead to the goal",
+        );
+
+        assert!(
+            movements <= 4,
+            "the field must follow a shortest path",
+        );
+
+        let field = nodes.generate_flow_field(&[0, 11])
+            .unwrap();
+
+        assert_eq!(
+            (field.get_distance(10), field.get_direction(10)),
+            (10, 0),
+            "the node must move to the nearest goal on the right",
+        );
+
+        assert_eq!(
+            field.get_distance(1),
+            10,
+            "the node must still move to the nearest goal on the left",
+        );
+
+        let field = nodes.generate_flow_field(&[2])
+            .unwrap();
+
+        assert!(
+            field.get_distances().iter().all(|d| *d == UNREACHABLE),
+            "a blocked goal must be ignored",
+        );
+
+        assert_eq!(
+            nodes.generate_flow_field(&[0, 12]),
+            Err(PathError::OutOfBounds),
+            "the goal is outside of the grid",
+        );
+    }
+
+    #[test]
+    fn test_astar_grid_flow_field() {
+
+        const NODES_AMOUNT: usize = 12;
+
+        let goals: [u32; 1] = [0];
+        let mut distances: [u32; NODES_AMOUNT] = [0; NODES_AMOUNT];
+        let mut directions: [i8; NODES_AMOUNT] = [0; NODES_AMOUNT];
+
+        unsafe {
+
+            let grid = astar_grid_new(4, 3);
+
+            for wall in [2, 6, 7].iter() {
+                astar_grid_set_blocked(grid, *wall, true);
+            }
+
+            assert_eq!(
+                astar_grid_flow_field(
+                    grid,
+                    goals.as_ptr(),
+                    goals.len(),
+                    distances.as_mut_ptr(),
+                    directions.as_mut_ptr(),
+                    NODES_AMOUNT,
+                ),
+                0,
+                "unexpected status code",
+            );
+
+            assert_eq!(
+                (distances[1], directions[1]),
+                (10, ASTAR_DIRECTION_LEFT),
+                "the node must move to the left",
+            );
+
+            assert_eq!(
+                (distances[0], directions[0]),
+                (0, ASTAR_DIRECTION_NONE),
+                "the goal has no direction",
+            );
+
+            assert_eq!(
+                distances[3],
+                ASTAR_UNREACHABLE_DISTANCE,
+                "the enclosed node cannot reach the goal",
+            );
+
+            assert_eq!(
+                astar_grid_flow_field(
+                    grid,
+                    goals.as_ptr(),
+                    goals.len(),
+                    distances.as_mut_ptr(),
+                    directions.as_mut_ptr(),
+                    NODES_AMOUNT - 1,
+                ),
+                ASTAR_ERROR_BUFFER_TOO_SMALL,
+                "unexpected error code",
+            );
+
+            assert_eq!(
+                astar_grid_flow_field(
+                    grid,
+                    [12].as_ptr(),
+                    1,
+                    distances.as_mut_ptr(),
+                    directions.as_mut_ptr(),
+                    NODES_AMOUNT,
+                ),
+                ASTAR_ERROR_OUT_OF_BOUNDS,
+                "unexpected error code",
+            );
+
+            assert_eq!(
+                astar_grid_flow_field(
+                    grid,
+                    ptr::null(),
+                    1,
+                    distances.as_mut_ptr(),
+                    directions.as_mut_ptr(),
+                    NODES_AMOUNT,
+                ),
+                ASTAR_ERROR_NULL_POINTER,
+                "unexpected error code",
+            );
+
+            astar_grid_free(grid);
+        }
+    }
+
+    /// Checks that a path is made of allowed movements from the departure
+    /// to the arrival, and returns its cost.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    /// * `departure` - the departure cell index
+    /// * `path` - the researched path, departure excluded
+    ///
+    /// # Returns:
+    ///
+    /// The sum of the movement costs of the path.
+    fn get_path_cost(
+        grid: &Grid,
+        departure: usize,
+        path: &[usize],
+    ) -> u32 {
+
+        let mut children = Vec::new();
+        let mut previous = departure;
+        let mut cost = 0;
+
+        for index in path.iter() {
+
+            grid.generate_children_list(previous, &mut children);
+
+            assert!(
+                children.contains(index) && grid.get_node(*index).is_usuable(),
+                "the path contains a forbidden movement",
+            );
+
+            cost += grid.get_movement_cost(previous, *index);
+            previous = *index;
+        }
+
+        cost
+    }
+
+    /// Creates a linear congruential generator,
+    /// so the random maps are the same every run.
+    ///
+    /// # Arguments:
+    ///
+    /// * `seed` - the first state of the generator
+    ///
+    /// # Returns:
+    ///
+    /// A function returning a number between 0 and the given maximum
+    /// (excluded).
+    fn get_random_generator(seed: u64) -> impl FnMut(usize) -> usize {
+
+        let mut seed = seed;
+
+        move |maximum: usize| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((seed >> 33) as usize) % maximum
+        }
+    }
+
+    /// Generates the terrain weights of a random map.
+    ///
+    /// # Arguments:
+    ///
+    /// * `random` - the random number generator
+    /// * `nodes_amount` - the amount of nodes of the map
+    /// * `walls_percentage` - the percentage of unusuable nodes
+    /// * `weighted_percentage` - the percentage of nodes weighted
+    ///   from 2 to `max_weight`
+    /// * `max_weight` - the largest weight of the map
+    ///
+    /// # Returns:
+    ///
+    /// The weight of every node, 0 for the walls.
+    fn generate_random_weights<F: FnMut(usize) -> usize>(
+        random: &mut F,
+        nodes_amount: usize,
+        walls_percentage: usize,
+        weighted_percentage: usize,
+        max_weight: u8,
+    ) -> Vec<u8> {
+
+        (0..nodes_amount)
+            .map(|_| {
+
+                let percentage = random(100);
+
+                if percentage < walls_percentage {
+                    0
+                } else if percentage < walls_percentage + weighted_percentage {
+                    random(max_weight as usize - 1) as u8 + 2
+                } else {
+                    1
+                }
+            })
+            .collect()
+    }
+
+    /// Researches one path with A*, with the jump point research
+    /// and with the precomputed jumps, and checks that the three
+    /// researches return paths as cheap or the same error.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    /// * `table` - the jumps generated from the grid
+    /// * `departure` - the departure cell index
+    /// * `arrival` - the arrival cell index
+    fn assert_jump_point_research(
+        grid: &Grid,
+        table: &JumpTable,
+        departure: usize,
+        arrival: usize,
+    ) {
+
+        let mut search = SearchContext::new();
+
+        let expected = search.research_path(grid, departure, arrival)
+            .map(|path| get_path_cost(grid, departure, &path));
+
+        search.set_algorithm(SearchAlgorithm::JumpPoint);
+
+        let jump_point = search.research_path(grid, departure, arrival)
+            .map(|path| {
+                assert_eq!(
+                    path.last(),
+                    Some(&arrival),
+                    "the path must end on the arrival",
+                );
+                get_path_cost(grid, departure, &path)
+            });
+
+        assert_eq!(
+            jump_point,
+            expected,
+            "the jump point path must be as cheap as the A* one",
+        );
+
+        let jump_table = search.research_path_with_jump_table(
+            grid,
+            table,
+            departure,
+            arrival,
+        ).map(|path| get_path_cost(grid, departure, &path));
+
+        assert_eq!(
+            jump_table,
+            expected,
+            "the JPS+ path must be as cheap as the A* one",
+        );
+    }
+
+    #[test]
+    fn test_jump_point_research_scenarios() {
+
+        /* width, height and walls of the previous tests */
+        let scenarios: [(usize, usize, &[usize]); 4] = [
+            (6, 6, &[2, 8, 14, 20, 26]),
+            (6, 6, &[28, 29, 34]),
+            (6, 6, &[7, 8, 9, 10, 25, 26, 27, 28, 32]),
+            (12, 12, &[]),
+        ];
+
+        for movement in [
+            Movement::EightDirections,
+            Movement::EightDirectionsNoCornerCutting,
+        ].iter() {
+
+            for &(width, height, walls) in scenarios.iter() {
+
+                let mut grid = Grid::new(width, height);
+                grid.set_movement(*movement);
+
+                for wall in walls.iter() {
+                    grid.set_blocked(*wall, true)
+                        .unwrap();
+                }
+
+                let table = JumpTable::new(&grid)
+                    .unwrap();
+
+                for departure in 0..width * height {
+                    for arrival in 0..width * height {
+                        assert_jump_point_research(
+                            &grid,
+                            &table,
+                            departure,
+                            arrival,
+                        );
+                    }
+                }
+            }
+        }
+
+        const WIDTH: usize = 40;
+        const ARRIVAL_INDEX: usize = WIDTH * 30 + 20;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            WIDTH,
+            0,
+            ARRIVAL_INDEX,
+        );
+
+        nodes.set_algorithm(SearchAlgorithm::JumpPoint);
+
+        let path = nodes.research_path()
+            .unwrap();
+
+        assert_eq!(
+            path.len(),
+            30,
+            "unexpected path length",
+        );
+
+        assert_eq!(
+            nodes.get_cost(ARRIVAL_INDEX),
+            20 * 14 + 10 * 10,
+            "unexpected path cost",
+        );
+
+        assert!(
+            nodes.get_closed_list().len() < 10,
+            "the jump point research must not close every node on the way",
+        );
+    }
+
+    #[test]
+    fn test_jump_point_research_random_maps() {
+
+        const WIDTH: usize = 24;
+        const HEIGHT: usize = 16;
+        const MAPS: usize = 12;
+        const RESEARCHES: usize = 40;
+
+        let mut random = get_random_generator(0x2545_f491);
+
+        for map in 0..MAPS {
+
+            let mut grid = Grid::new(WIDTH, HEIGHT);
+
+            if map % 2 == 1 {
+                grid.set_movement(Movement::EightDirectionsNoCornerCutting);
+            }
+
+            /* from 10% to 40% of walls */
+            let weights = generate_random_weights(
+                &mut random,
+                WIDTH * HEIGHT,
+                10 + map * 30 / MAPS,
+                0,
+                1,
+            );
+
+            grid.set_weights(&weights)
+                .unwrap();
+
+            let table = JumpTable::new(&grid)
+                .unwrap();
+
+            for _ in 0..RESEARCHES {
+                assert_jump_point_research(
+                    &grid,
+                    &table,
+                    random(WIDTH * HEIGHT),
+                    random(WIDTH * HEIGHT),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_jump_point_unsupported_grids() {
+
+        let mut nodes = Nodes::new(8, 8, 0, 63);
+        nodes.set_algorithm(SearchAlgorithm::JumpPoint);
+
+        assert!(
+            jump_point::is_grid_supported(nodes.get_grid()),
+            "the default grid must be supported",
+        );
+
+        nodes.set_movement(Movement::FourDirections);
+
+        assert_eq!(
+            nodes.research_path(),
+            Err(PathError::UnsupportedGrid),
+            "the jump point research needs diagonal movements",
+        );
+
+        assert_eq!(
+            nodes.generate_jump_table(),
+            Err(PathError::UnsupportedGrid),
+            "the jump point research needs diagonal movements",
+        );
+
+        nodes.set_movement(Movement::EightDirections);
+        nodes.set_weight(9, 3)
+            .unwrap();
+
+        assert_eq!(
+            nodes.research_path(),
+            Err(PathError::UnsupportedGrid),
+            "the jump point research needs uniform weights",
+        );
+
+        /* a uniform weight different from 1 is supported */
+        nodes.set_weights(&[2; 64])
+            .unwrap();
+        nodes.set_move_costs(MoveCosts::new(10, 25));
+
+        assert_eq!(
+            nodes.research_path(),
+            Err(PathError::UnsupportedGrid),
+            "a diagonal movement must cost less than two straight ones",
+        );
+
+        nodes.set_move_costs(MoveCosts::default());
+        nodes.generate_jump_table()
+            .unwrap();
+
+        assert_eq!(
+            nodes.research_path()
+                .map(|path| path.len()),
+            Ok(7),
+            "unexpected path length",
+        );
+
+        assert_eq!(
+            nodes.get_cost(63),
+            7 * 14 * 2,
+            "unexpected path cost",
+        );
+
+        /* blocking a node drops the precomputed jumps */
+        for index in [9, 18, 27, 36, 45, 54].iter() {
+            nodes.set_blocked(*index, true)
+                .unwrap();
+        }
+
+        let path = nodes.research_path()
+            .unwrap();
+
+        assert_eq!(
+            get_path_cost(nodes.get_grid(), 0, &path),
+            SearchContext::new()
+                .research_path(nodes.get_grid(), 0, 63)
+                .map(|path| get_path_cost(nodes.get_grid(), 0, &path))
+                .unwrap(),
+            "the path must avoid the new walls",
+        );
+
+        /* changing the movement costs drops the precomputed jumps too */
+        nodes.generate_jump_table()
+            .unwrap();
+        nodes.set_move_costs(MoveCosts::new(10, 25));
+
+        assert_eq!(
+            nodes.research_path(),
+            Err(PathError::UnsupportedGrid),
+            "the precomputed jumps must not outlive the movement costs",
+        );
+    }
+
+    #[test]
+    fn test_outdated_jump_table() {
+
+        const WIDTH: usize = 8;
+        let mut grid = Grid::new(WIDTH, WIDTH);
+        let mut search = SearchContext::new();
+
+        let modifications: [&dyn Fn(&mut Grid); 5] = [
+            &|grid| grid.set_blocked(WIDTH + 1, true).unwrap(),
+            &|grid| grid.set_blocked(WIDTH + 1, false).unwrap(),
+            &|grid| grid.set_move_costs(MoveCosts::new(10, 15)),
+            &|grid| grid.set_movement(Movement::EightDirectionsNoCornerCutting),
+            &|grid| grid.get_node_by_index(9).set_unusuable(),
+        ];
+
+        for modify in modifications.iter() {
+
+            let table = JumpTable::new(&grid)
+                .unwrap();
+
+            assert!(
+                search.research_path_with_jump_table(&grid, &table, 0, 63)
+                    .is_ok(),
+                "the table must be used on the grid it comes from",
+            );
+
+            modify(&mut grid);
+
+            assert_eq!(
+                search.research_path_with_jump_table(&grid, &table, 0, 63),
+                Err(PathError::UnsupportedGrid),
+                "an outdated table must not be used",
+            );
+        }
+
+        /* the support of the grid is checked again after a modification */
+        assert!(
+            jump_point::is_grid_supported(&grid),
+            "unexpected grid support",
+        );
+
+        grid.set_weight(20, 3)
+            .unwrap();
+
+        assert!(
+            !jump_point::is_grid_supported(&grid),
+            "the weights are not uniform anymore",
+        );
+
+        grid.set_weight(20, 1)
+            .unwrap();
+
+        assert!(
+            jump_point::is_grid_supported(&grid),
+            "the weights are uniform again",
+        );
+    }
+
+    #[test]
+    fn test_bidirectional_research_random_maps() {
+
+        const WIDTH: usize = 20;
+        const HEIGHT: usize = 14;
+        const RESEARCHES: usize = 30;
+
+        let movements = [
+            Movement::FourDirections,
+            Movement::EightDirections,
+            Movement::EightDirectionsNoCornerCutting,
+            Movement::EightDirectionsNoSqueezing,
+        ];
+
+        let mut random = get_random_generator(0x5eed);
+
+        for map in 0..12 {
+
+            let mut grid = Grid::new(WIDTH, HEIGHT);
+            grid.set_movement(movements[map % movements.len()]);
+
+            let weights = generate_random_weights(
+                &mut random,
+                WIDTH * HEIGHT,
+                30,
+                20,
+                9,
+            );
+
+            grid.set_weights(&weights)
+                .unwrap();
+
+            let mut search = SearchContext::new();
+            let mut bidirectional = SearchContext::new();
+            bidirectional.set_algorithm(SearchAlgorithm::Bidirectional);
+
+            for _ in 0..RESEARCHES {
+
+                let departure = random(WIDTH * HEIGHT);
+                let arrival = random(WIDTH * HEIGHT);
+
+                let expected = search.research_path(&grid, departure, arrival)
+                    .map(|path| get_path_cost(&grid, departure, &path));
+
+                let result = bidirectional.research_path(
+                    &grid,
+                    departure,
+                    arrival,
+                ).map(|path| {
+                    assert_eq!(
+                        path.last(),
+                        Some(&arrival),
+                        "the path must end on the arrival",
+                    );
+                    get_path_cost(&grid, departure, &path)
+                });
+
+                assert_eq!(
+                    result,
+                    expected,
+                    "the bidirectional path must be as cheap as the A* one",
+                );
+
+                if let Ok(cost) = result {
+                    assert_eq!(
+                        bidirectional.get_cost(arrival),
+                        cost,
+                        "the arrival cost must be the path cost",
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bidirectional_research_expansions() {
+
+        const WIDTH: usize = 64;
+        const DEPARTURE_INDEX: usize = WIDTH * 32 + 4;
+        const ARRIVAL_INDEX: usize = WIDTH * 32 + 50;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            WIDTH,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        /* the arrival is inside of a cup only open on the right side,
+           A* fills the area in front of the cup before going around */
+        for line in 20..45 {
+            nodes.set_blocked(line * WIDTH + 44, true)
+                .unwrap();
+        }
+
+        for column in 44..58 {
+            nodes.set_blocked(20 * WIDTH + column, true)
+                .unwrap();
+            nodes.set_blocked(44 * WIDTH + column, true)
+                .unwrap();
+        }
+
+        let path = nodes.research_path()
+            .unwrap();
+        let cost = nodes.get_cost(ARRIVAL_INDEX);
+
+        assert_eq!(
+            nodes.get_arrival_side_expansions(),
+            0,
+            "A* only expands from the departure",
+        );
+
+        let a_star_expansions = nodes.get_departure_side_expansions();
+
+        nodes.set_algorithm(SearchAlgorithm::Bidirectional);
+
+        let bidirectional_path = nodes.research_path()
+            .unwrap();
+
+        assert_eq!(
+            nodes.get_cost(ARRIVAL_INDEX),
+            cost,
+            "the bidirectional path must be as cheap as the A* one",
+        );
+
+        assert_eq!(
+            get_path_cost(nodes.get_grid(), DEPARTURE_INDEX, &path),
+            get_path_cost(
+                nodes.get_grid(),
+                DEPARTURE_INDEX,
+                &bidirectional_path,
+            ),
+            "the paths must be as cheap",
+        );
+
+        let departure_side = nodes.get_departure_side_expansions();
+        let arrival_side = nodes.get_arrival_side_expansions();
+
+        assert!(
+            departure_side > 0 && arrival_side > 0,
+            "both sides must be expanded",
+        );
+
+        assert!(
+            departure_side + arrival_side < a_star_expansions,
+            "both sides must expand fewer nodes than A* around the cup",
+        );
+
+        /* the cup is closed, the arrival side explores its inside only */
+        for line in 20..45 {
+            nodes.set_blocked(line * WIDTH + 58, true)
+                .unwrap();
+        }
+
+        assert_eq!(
+            nodes.research_path(),
+            Err(PathError::Unreachable),
+            "unexpected research result",
+        );
+
+        const CUP_NODES: usize = 13 * 23;
+        assert_eq!(
+            nodes.get_arrival_side_expansions(),
+            CUP_NODES,
+            "the research must stop when the inside of the cup is explored",
+        );
+
+        assert!(
+            nodes.get_departure_side_expansions() < CUP_NODES,
+            "the departure side must not explore the whole grid",
+        );
+    }
+
+    #[test]
+    fn test_path_result() {
+
+        let mut nodes = Nodes::new(4, 1, 0, 3);
+
+        let result = nodes.research_path_result(true)
+            .unwrap();
+
+        assert_eq!(
+            result.get_path(),
+            &[1, 2, 3],
+            "unexpected path",
+        );
+
+        assert_eq!(
+            (result.get_cost(), result.get_length()),
+            (30, 3.0),
+            "unexpected cost or length",
+        );
+
+        assert_eq!(
+            (
+                result.get_expanded_amount(),
+                result.get_generated_amount(),
+                result.get_peak_open_amount(),
+            ),
+            (3, 3, 1),
+            "unexpected research statistics",
+        );
+
+        assert_eq!(
+            result.get_closed_list(),
+            Some(&[0, 1, 2, 3][..]),
+            "unexpected closed list",
+        );
+
+        assert_eq!(
+            nodes.research_path_result(false)
+                .unwrap()
+                .get_closed_list(),
+            None,
+            "the closed list must only be kept on demand",
+        );
+
+        const WIDTH: usize = 10;
+        const DEPARTURE_INDEX: usize = 0;
+        const ARRIVAL_INDEX: usize = 99;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            WIDTH,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        /* wall with a gap at the bottom */
+        for line in 0..WIDTH - 1 {
+            nodes.set_blocked(line * WIDTH + 5, true)
+                .unwrap();
+        }
+
+        for algorithm in [
+            SearchAlgorithm::AStar,
+            SearchAlgorithm::Dijkstra,
+            SearchAlgorithm::BreadthFirst,
+            SearchAlgorithm::GreedyBestFirst,
+            SearchAlgorithm::JumpPoint,
+            SearchAlgorithm::Bidirectional,
+            SearchAlgorithm::AStar,
+        ].iter() {
+
+            nodes.set_algorithm(*algorithm);
+
+            let result = nodes.research_path_result(true)
+                .unwrap();
+
+            assert_eq!(
+                result.get_path(),
+                &nodes.research_path().unwrap()[..],
+                "the path must be the one of research_path",
+            );
+
+            let path = result.get_path();
+
+            assert_eq!(
+                result.get_cost(),
+                get_path_cost(nodes.get_grid(), DEPARTURE_INDEX, path),
+                "the cost must be the one of the grid, whatever the algorithm",
+            );
+
+            let diagonals = Some(&DEPARTURE_INDEX)
+                .into_iter()
+                .chain(path.iter())
+                .zip(path.iter())
+                .filter(|(previous, index)| {
+                    *previous % WIDTH != *index % WIDTH &&
+                        *previous / WIDTH != *index / WIDTH
+                })
+                .count();
+
+            let length = (path.len() - diagonals) as f64 +
+                diagonals as f64 * 2.0_f64.sqrt();
+
+            assert!(
+                (result.get_length() - length).abs() < 1e-9,
+                "unexpected length",
+            );
+
+            assert_eq!(
+                result.get_expanded_amount(),
+                nodes.get_departure_side_expansions() +
+                    nodes.get_arrival_side_expansions(),
+                "the expansions of both sides must be counted",
+            );
+
+            let closed_list = result.get_closed_list()
+                .unwrap();
+
+            assert!(
+                closed_list.contains(&DEPARTURE_INDEX) &&
+                    closed_list.contains(&ARRIVAL_INDEX),
+                "the departure and the arrival must be closed",
+            );
+
+            assert!(
+                closed_list.len() <= result.get_expanded_amount() + 1,
+                "only the expanded nodes and the arrival can be closed",
+            );
+
+            assert!(
+                result.get_generated_amount() + 2 >= closed_list.len() &&
+                    result.get_peak_open_amount() > 0 &&
+                    result.get_peak_open_amount() <=
+                        result.get_generated_amount(),
+                "unexpected generated amount or open list peak",
+            );
+
+            if *algorithm != SearchAlgorithm::Bidirectional {
+                assert_eq!(
+                    closed_list,
+                    &nodes.get_closed_list()[..],
+                    "unexpected closed list",
+                );
+            }
+        }
+
+        nodes.set_blocked(95, true)
+            .unwrap();
+
+        assert_eq!(
+            nodes.research_path_result(true),
+            Err(PathError::Unreachable),
+            "unexpected research result",
+        );
+    }
+
+    #[test]
+    fn test_resumable_search() {
+
+        const WIDTH: usize = 64;
+        const DEPARTURE_INDEX: usize = WIDTH * 32 + 4;
+        const ARRIVAL_INDEX: usize = WIDTH * 32 + 50;
+        const MAX_EXPANSIONS: usize = 10;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            WIDTH,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        /* the arrival is inside of a cup only open on the right side */
+        for line in 20..45 {
+            nodes.set_blocked(line * WIDTH + 44, true)
+                .unwrap();
+        }
+
+        for column in 44..58 {
+            nodes.set_blocked(20 * WIDTH + column, true)
+                .unwrap();
+            nodes.set_blocked(44 * WIDTH + column, true)
+                .unwrap();
+        }
+
+        let get_expansions = |search: &ResumableSearch| {
+            let context = search.get_search_context();
+            context.get_departure_side_expansions() +
+                context.get_arrival_side_expansions()
+        };
+
+        for algorithm in [
+            SearchAlgorithm::AStar,
+            SearchAlgorithm::Dijkstra,
+            SearchAlgorithm::BreadthFirst,
+            SearchAlgorithm::GreedyBestFirst,
+            SearchAlgorithm::JumpPoint,
+            SearchAlgorithm::Bidirectional,
+        ].iter() {
+
+            nodes.set_algorithm(*algorithm);
+
+            let path = nodes.research_path()
+                .unwrap();
+            let expansions = nodes.get_departure_side_expansions() +
+                nodes.get_arrival_side_expansions();
+
+            let mut search = nodes.create_resumable_search();
+            let started_expansions = get_expansions(&search);
+
+            assert_eq!(
+                search.step(nodes.get_grid(), 0),
+                Progress::Running,
+                "no node must be expanded without budget",
+            );
+
+            assert_eq!(
+                get_expansions(&search),
+                started_expansions,
+                "no node must be expanded without budget",
+            );
+
+            let mut progress = Progress::Running;
+
+            while progress == Progress::Running {
+
+                let previous_expansions = get_expansions(&search);
+
+                progress = search.step(nodes.get_grid(), MAX_EXPANSIONS);
+
+                assert!(
+                    get_expansions(&search) - previous_expansions <=
+                        MAX_EXPANSIONS,
+                    "a step must not exceed its budget",
+                );
+            }
+
+            assert_eq!(
+                progress,
+                Progress::Found(path.clone()),
+                "the path must be the one of the complete research",
+            );
+
+            assert_eq!(
+                get_expansions(&search),
+                expansions,
+                "the steps must expand the nodes of the complete research",
+            );
+
+            assert_eq!(
+                search.step(nodes.get_grid(), MAX_EXPANSIONS),
+                Progress::Found(path.clone()),
+                "a research over must return its result again",
+            );
+
+            /* cancelled in the middle, then restarted from the beginning */
+            search.restart(nodes.get_grid(), DEPARTURE_INDEX, ARRIVAL_INDEX);
+            search.step(nodes.get_grid(), MAX_EXPANSIONS);
+            search.cancel();
+
+            assert_eq!(
+                search.step(nodes.get_grid(), MAX_EXPANSIONS),
+                Progress::NoPath(PathError::Cancelled),
+                "a cancelled research must not continue",
+            );
+
+            search.restart(nodes.get_grid(), DEPARTURE_INDEX, ARRIVAL_INDEX);
+
+            assert_eq!(
+                search.step(nodes.get_grid(), usize::MAX),
+                Progress::Found(path),
+                "a restarted research must find the same path",
+            );
+
+            search.restart(nodes.get_grid(), DEPARTURE_INDEX, DEPARTURE_INDEX);
+
+            assert_eq!(
+                search.get_progress(),
+                &Progress::NoPath(PathError::DepartureIsArrival),
+                "invalid queries must be over at once",
+            );
+        }
+
+        /* the cup is closed */
+        for line in 20..45 {
+            nodes.set_blocked(line * WIDTH + 58, true)
+                .unwrap();
+        }
+
+        nodes.set_algorithm(SearchAlgorithm::AStar);
+
+        let mut search = nodes.create_resumable_search();
+        let mut progress = Progress::Running;
+
+        while progress == Progress::Running {
+            progress = search.step(nodes.get_grid(), MAX_EXPANSIONS);
+        }
+
+        assert_eq!(
+            progress,
+            Progress::NoPath(PathError::Unreachable),
+            "unexpected research result",
+        );
+
+        /* a smaller grid is rejected instead of being read out of bounds */
+        let small_grid = Grid::new(WIDTH / 2, WIDTH / 2);
+
+        search.restart(nodes.get_grid(), DEPARTURE_INDEX, ARRIVAL_INDEX);
+
+        assert_eq!(
+            search.step(&small_grid, MAX_EXPANSIONS),
+            Progress::NoPath(PathError::OutOfBounds),
+            "the grid must have the dimensions of the research",
+        );
+
+        assert_eq!(
+            search.step(nodes.get_grid(), MAX_EXPANSIONS),
+            Progress::Running,
+            "the research must go on with its own grid",
+        );
+
+        search.restart(&small_grid, 0, 1);
+
+        assert_eq!(
+            search.get_progress(),
+            &Progress::NoPath(PathError::OutOfBounds),
+            "the grid must have the dimensions of the research",
+        );
+    }
+
+    #[test]
+    fn test_d_star_lite_repairs_paths() {
+
+        const WIDTH: usize = 18;
+        const HEIGHT: usize = 12;
+        const CHANGES: usize = 25;
+
+        let movements = [
+            Movement::FourDirections,
+            Movement::EightDirections,
+            Movement::EightDirectionsNoCornerCutting,
+            Movement::EightDirectionsNoSqueezing,
+        ];
+
+        let mut random = get_random_generator(0xd5a7);
+
+        for movement in movements.iter() {
+
+            let mut nodes = Nodes::new(WIDTH, HEIGHT, 0, WIDTH * HEIGHT - 1);
+            nodes.set_movement(*movement);
+
+            let mut planner = nodes.create_planner()
+                .unwrap();
+            let mut search = SearchContext::new();
+            let goal = planner.get_goal_index();
+
+            for _ in 0..CHANGES {
+
+                /* a wall is built or destroyed, or the terrain changes */
+                let index = random(WIDTH * HEIGHT);
+
+                if index != goal && index != planner.get_start_index() {
+
+                    match random(3) {
+                        0 => nodes.set_blocked(index, true),
+                        1 => nodes.set_blocked(index, false),
+                        _ => nodes.set_weight(index, random(5) as u8 + 1),
+                    }.unwrap();
+
+                    planner.notify_cell_changed(nodes.get_grid(), index)
+                        .unwrap();
+                }
+
+                let start = planner.get_start_index();
+                let grid = nodes.get_grid();
+
+                let expected = search.research_path(grid, start, goal)
+                    .map(|path| get_path_cost(grid, start, &path));
+
+                let result = planner.research_path(grid);
+
+                assert_eq!(
+                    result.as_ref()
+                        .map(|path| get_path_cost(grid, start, path))
+                        .map_err(|error| *error),
+                    expected,
+                    "the repaired path must be as cheap as the A* one",
+                );
+
+                /* the unit moves one node along its path */
+                if let Ok(path) = result {
+
+                    assert_eq!(
+                        planner.get_cost(start),
+                        get_path_cost(grid, start, &path),
+                        "the start cost must be the path cost",
+                    );
+
+                    if path.len() > 1 {
+                        planner.set_start_index(grid, path[0])
+                            .unwrap();
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_d_star_lite_incremental_research() {
+
+        const WIDTH: usize = 64;
+        const DEPARTURE_INDEX: usize = WIDTH * 10 + 2;
+        const ARRIVAL_INDEX: usize = WIDTH * 54 + 60;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            WIDTH,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        for line in 0..48 {
+            nodes.set_blocked(line * WIDTH + 32, true)
+                .unwrap();
+        }
+
+        let mut planner = nodes.create_planner()
+            .unwrap();
+
+        let path = planner.research_path(nodes.get_grid())
+            .unwrap();
+        let first_expansions = planner.get_expanded_amount();
+
+        assert_eq!(
+            path.last(),
+            Some(&ARRIVAL_INDEX),
+            "the path must end on the goal",
+        );
+
+        /* the unit walks, then a wall is built on its way */
+        let position = path[4];
+        planner.set_start_index(nodes.get_grid(), position)
+            .unwrap();
+
+        let wall = path[20];
+        nodes.set_blocked(wall, true)
+            .unwrap();
+        planner.notify_cell_changed(nodes.get_grid(), wall)
+            .unwrap();
+
+        let repaired_path = planner.research_path(nodes.get_grid())
+            .unwrap();
+
+        assert!(
+            !repaired_path.contains(&wall),
+            "the path must avoid the new wall",
+        );
+
+        assert_eq!(
+            nodes.get_search_context()
+                .clone()
+                .research_path(nodes.get_grid(), position, ARRIVAL_INDEX)
+                .map(|path| get_path_cost(nodes.get_grid(), position, &path)),
+            Ok(planner.get_cost(position)),
+            "the repaired path must be as cheap as the A* one",
+        );
+
+        assert!(
+            planner.get_expanded_amount() * 4 < first_expansions,
+            "the repair must expand far fewer nodes than the first research",
+        );
+
+        /* the wall closes the way, then opens again */
+        for line in 48..WIDTH {
+            nodes.set_blocked(line * WIDTH + 32, true)
+                .unwrap();
+            planner.notify_cell_changed(nodes.get_grid(), line * WIDTH + 32)
+                .unwrap();
+        }
+
+        assert_eq!(
+            planner.research_path(nodes.get_grid()),
+            Err(PathError::Unreachable),
+            "unexpected research result",
+        );
+
+        nodes.set_blocked(WIDTH * 60 + 32, false)
+            .unwrap();
+        planner.notify_cell_changed(nodes.get_grid(), WIDTH * 60 + 32)
+            .unwrap();
+
+        assert!(
+            planner.research_path(nodes.get_grid())
+                .unwrap()
+                .contains(&(WIDTH * 60 + 32)),
+            "the path must go through the opened door",
+        );
+    }
+
+    #[test]
+    fn test_hierarchical_research_random_maps() {
+
+        const WIDTH: usize = 40;
+        const HEIGHT: usize = 28;
+        const RESEARCHES: usize = 30;
+        const CHANGES: usize = 20;
+
+        let movements = [
+            Movement::FourDirections,
+            Movement::EightDirections,
+            Movement::EightDirectionsNoCornerCutting,
+            Movement::EightDirectionsNoSqueezing,
+        ];
+
+        let mut random = get_random_generator(0x4a9f);
+
+        let mut total_cost = 0;
+        let mut total_expected_cost = 0;
+
+        for movement in movements.iter() {
+            for cluster_size in [3, 8].iter() {
+
+                let mut nodes = Nodes::new(WIDTH, HEIGHT, 0, 1);
+                nodes.set_movement(*movement);
+
+                let weights = generate_random_weights(
+                    &mut random,
+                    WIDTH * HEIGHT,
+                    30,
+                    10,
+                    5,
+                );
+
+                nodes.set_weights(&weights)
+                    .unwrap();
+
+                let mut hierarchy = nodes.create_hierarchy(*cluster_size);
+                let mut search = SearchContext::new();
+
+                for research in 0..RESEARCHES {
+
+                    /* the changes are taken into account incrementally */
+                    if research < CHANGES {
+
+                        let index = random(WIDTH * HEIGHT);
+                        nodes.set_blocked(index, random(2) == 0)
+                            .unwrap();
+                        hierarchy.notify_cell_changed(nodes.get_grid(), index)
+                            .unwrap();
+                    }
+
+                    let grid = nodes.get_grid();
+                    let departure = random(WIDTH * HEIGHT);
+                    let arrival = random(WIDTH * HEIGHT);
+
+                    let expected = search.research_path(grid, departure, arrival)
+                        .map(|path| get_path_cost(grid, departure, &path));
+
+                    let result = hierarchy.research_path(grid, departure, arrival);
+
+                    assert_eq!(
+                        result,
+                        HierarchicalGrid::new(grid, *cluster_size)
+                            .research_path(grid, departure, arrival),
+                        "the updated graph must be the rebuilt one",
+                    );
+
+                    let cost = result.map(|path| {
+
+                        assert_eq!(
+                            path.last(),
+                            Some(&arrival),
+                            "the path must end on the arrival",
+                        );
+
+                        get_path_cost(grid, departure, &path)
+                    });
+
+                    assert_eq!(
+                        cost.map(|_| ()),
+                        expected.map(|_| ()),
+                        "the arrival must be reached like with A*",
+                    );
+
+                    if let (Ok(cost), Ok(expected)) = (cost, expected) {
+                        assert!(
+                            cost >= expected,
+                            "the path cannot be cheaper than the A* one",
+                        );
+
+                        total_cost += cost;
+                        total_expected_cost += expected;
+                    }
+                }
+            }
+        }
+
+        /* the entrances may lengthen the short paths a lot,
+           but the paths stay close to the cheapest ones on average */
+        assert!(
+            total_cost * 4 <= total_expected_cost * 5,
+            "the paths must be close to the cheapest ones",
+        );
+    }
+
+    #[test]
+    fn test_hierarchical_notify_cell_changed() {
+
+        const WIDTH: usize = 64;
+        const CLUSTER_SIZE: usize = 16;
+        const DEPARTURE_INDEX: usize = WIDTH * 8 + 4;
+        const ARRIVAL_INDEX: usize = WIDTH * 56 + 60;
+        let mut nodes = Nodes::new(
+            WIDTH,
+            WIDTH,
+            DEPARTURE_INDEX,
+            ARRIVAL_INDEX,
+        );
+
+        let mut hierarchy = nodes.create_hierarchy(CLUSTER_SIZE);
+
+        assert_eq!(
+            hierarchy.get_cluster(WIDTH * 17 + 33),
+            6,
+            "unexpected cluster",
+        );
+
+        /* every border of an empty map has one opening of 16 nodes,
+           the entrances at its ends are shared with the crossing borders */
+        assert_eq!(
+            hierarchy.get_entrances_amount(),
+            4 * 3 + 12 * 4,
+            "unexpected amount of entrances",
+        );
+
+        /* a wall between the two halves, opened at the bottom */
+        for line in 0..WIDTH - 4 {
+            nodes.set_blocked(line * WIDTH + 31, true)
+                .unwrap();
+            hierarchy.notify_cell_changed(nodes.get_grid(), line * WIDTH + 31)
+                .unwrap();
+        }
+
+        let grid = nodes.get_grid();
+        let path = hierarchy.research_path(grid, DEPARTURE_INDEX, ARRIVAL_INDEX)
+            .unwrap();
+
+        assert!(
+            path.iter().any(|index| *index % WIDTH == 31),
+            "the path must go through the opening",
+        );
+
+        assert_eq!(
+            hierarchy.research_path(grid, DEPARTURE_INDEX, DEPARTURE_INDEX),
+            Err(PathError::DepartureIsArrival),
+            "unexpected research result",
+        );
+
+        assert_eq!(
+            hierarchy.notify_cell_changed(grid, WIDTH * WIDTH),
+            Err(PathError::OutOfBounds),
+            "unexpected notification result",
+        );
+
+        /* the wall closes the way */
+        for line in WIDTH - 4..WIDTH {
+            nodes.set_blocked(line * WIDTH + 31, true)
+                .unwrap();
+            hierarchy.notify_cell_changed(nodes.get_grid(), line * WIDTH + 31)
+                .unwrap();
+        }
+
+        assert_eq!(
+            hierarchy.research_path(
+                nodes.get_grid(),
+                DEPARTURE_INDEX,
+                ARRIVAL_INDEX,
+            ),
+            Err(PathError::Unreachable),
+            "unexpected research result",
+        );
+    }
+
+    /// Checks that every segment of an any-angle path only goes
+    /// through usuable nodes, by sampling points along the segments.
+    ///
+    /// # Arguments:
+    ///
+    /// * `grid` - the grid of the research
+    /// * `departure` - the departure cell index
+    /// * `waypoints` - the waypoints of the path, departure excluded
+    ///
+    /// # Returns:
+    ///
+    /// The euclidean length of the path.
+    fn check_any_angle_path(
+        grid: &Grid,
+        departure: usize,
+        waypoints: &[usize],
+    ) -> f64 {
+
+        const SAMPLES_PER_NODE: usize = 64;
+
+        let width = grid.get_width();
+        let mut length = 0.0;
+        let mut previous = departure;
+
+        for waypoint in waypoints.iter() {
+
+            let from_x = (previous % width) as f64 + 0.5;
+            let from_y = (previous / width) as f64 + 0.5;
+            let to_x = (*waypoint % width) as f64 + 0.5;
+            let to_y = (*waypoint / width) as f64 + 0.5;
+
+            let segment_length =
+                ((to_x - from_x).powi(2) + (to_y - from_y).powi(2)).sqrt();
+            let samples = (segment_length * SAMPLES_PER_NODE as f64) as usize;
+
+            for sample in 0..samples {
+
+                let ratio = sample as f64 / samples as f64;
+                let x = from_x + (to_x - from_x) * ratio;
+                let y = from_y + (to_y - from_y) * ratio;
+
+                /* the corners follow the movement model */
+                if x.fract() == 0.0 && y.fract() == 0.0 {
+                    continue;
+                }
+
+                assert!(
+                    grid.get_node(y as usize * width + x as usize)
+                        .is_usuable(),
+                    "the segments must only go through usuable nodes",
+                );
+            }
+
+            length += segment_length;
+            previous = *waypoint;
+        }
+
+        length
+    }
+
+    #[test]
+    fn test_any_angle_research() {
+
+        const WIDTH: usize = 12;
+        const DEPARTURE_INDEX: usize = WIDTH + 1;
+        const ARRIVAL_INDEX: usize = WIDTH * 5 + 10;
+        const WALL_INDEX: usize = WIDTH * 3 + 6;
+
+        let algorithms = [
+            AnyAngleAlgorithm::ThetaStar,
+            AnyAngleAlgorithm::LazyThetaStar,
+        ];
+
+        let mut nodes = Nodes::new(WIDTH, 8, DEPARTURE_INDEX, ARRIVAL_INDEX);
+
+        for algorithm in algorithms.iter() {
+
+            let path = nodes.research_any_angle_path(*algorithm)
+                .unwrap();
+
+            assert_eq!(
+                path.get_waypoints(),
+                &[ARRIVAL_INDEX],
+                "the arrival must be reached in a straight line",
+            );
+
+            assert!(
+                (path.get_length() - (81.0f64 + 16.0).sqrt()).abs() < 1e-9,
+                "the length must be the euclidean distance",
+            );
+        }
+
+        /* a wall in the way, the path turns after its end
+           (the corner would be cut with the default movement) */
+        nodes.set_movement(Movement::EightDirectionsNoCornerCutting);
+
+        for line in 0..6 {
+            nodes.set_blocked(WIDTH * line + 6, true)
+                .unwrap();
+        }
+
+        for algorithm in algorithms.iter() {
+
+            let path = nodes.research_any_angle_path(*algorithm)
+                .unwrap();
+
+            assert_eq!(
+                path.get_waypoints()[0],
+                WIDTH * 6 + 5,
+                "the path must turn after the end of the wall",
+            );
+
+            /* the lazy variant may keep one more waypoint */
+            if *algorithm == AnyAngleAlgorithm::ThetaStar {
+                assert_eq!(
+                    path.get_waypoints(),
+                    &[WIDTH * 6 + 5, ARRIVAL_INDEX],
+                    "the path must turn once",
+                );
+            }
+
+            assert!(
+                (
+                    check_any_angle_path(
+                        nodes.get_grid(),
+                        DEPARTURE_INDEX,
+                        path.get_waypoints(),
+                    ) - path.get_length()
+                ).abs() < 1e-9,
+                "the length must be the euclidean length of the segments",
+            );
+        }
+
+        nodes.set_arrival_index(WALL_INDEX);
+
+        assert_eq!(
+            nodes.research_any_angle_path(AnyAngleAlgorithm::ThetaStar),
+            Err(PathError::ArrivalBlocked),
+            "unexpected research result",
+        );
+    }
+
+    #[test]
+    fn test_any_angle_research_random_maps() {
+
+        const WIDTH: usize = 30;
+        const HEIGHT: usize = 20;
+        const RESEARCHES: usize = 20;
+
+        let movements = [
+            Movement::FourDirections,
+            Movement::EightDirections,
+            Movement::EightDirectionsNoCornerCutting,
+            Movement::EightDirectionsNoSqueezing,
+        ];
+
+        let algorithms = [
+            AnyAngleAlgorithm::ThetaStar,
+            AnyAngleAlgorithm::LazyThetaStar,
+        ];
+
+        let mut random = get_random_generator(0x7e3a);
+
+        for movement in movements.iter() {
+
+            let mut grid = Grid::new(WIDTH, HEIGHT);
+            grid.set_movement(*movement);
+
+            /* the diagonal cost is just above the square root of 2,
+               the A* cost is longer than the cheapest octile path */
+            grid.set_move_costs(MoveCosts::new(1000, 1415));
+
+            let weights = generate_random_weights(
+                &mut random,
+                WIDTH * HEIGHT,
+                30,
+                0,
+                1,
+            );
+
+            grid.set_weights(&weights)
+                .unwrap();
+
+            let mut search = SearchContext::new();
+
+            for _ in 0..RESEARCHES {
+
+                let departure = random(WIDTH * HEIGHT);
+                let arrival = random(WIDTH * HEIGHT);
+
+                let expected = search.research_path(&grid, departure, arrival)
+                    .map(|path| get_path_cost(&grid, departure, &path));
+
+                for algorithm in algorithms.iter() {
+
+                    let result = research_any_angle_path(
+                        &grid,
+                        departure,
+                        arrival,
+                        *algorithm,
+                    );
+
+                    assert_eq!(
+                        result.as_ref().map(|_| ()).map_err(|error| *error),
+                        expected.map(|_| ()),
+                        "the arrival must be reached like with A*",
+                    );
+
+                    if let (Ok(path), Ok(cost)) = (result, expected) {
+
+                        assert_eq!(
+                            path.get_waypoints().last(),
+                            Some(&arrival),
+                            "the path must end on the arrival",
+                        );
+
+                        let length = check_any_angle_path(
+                            &grid,
+                            departure,
+                            path.get_waypoints(),
+                        );
+
+                        assert!(
+                            (length - path.get_length()).abs() < 1e-9,
+                            "the length must be the euclidean length",
+                        );
+
+                        /* the lazy variant may keep a worse parent
+                           when the line of sight fails */
+                        let tolerance = match *algorithm {
+                            AnyAngleAlgorithm::ThetaStar => 1.0,
+                            AnyAngleAlgorithm::LazyThetaStar => 1.1,
+                        };
+
+                        assert!(
+                            length <= cost as f64 / 1000.0 * tolerance,
+                            "the path cannot be longer than the A* one",
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_line_traversals() {
+
+        let points = |coordinates: &[(usize, usize)]| -> Vec<Point> {
+            coordinates.iter()
+                .map(|&(x, y)| Point::new(x, y))
+                .collect()
+        };
+
+        assert_eq!(
+            bresenham_line(Point::new(0, 0), Point::new(5, 2)),
+            points(&[(0, 0), (1, 0), (2, 1), (3, 1), (4, 2), (5, 2)]),
+            "unexpected Bresenham line",
+        );
+
+        assert_eq!(
+            bresenham_line(Point::new(2, 5), Point::new(1, 1)),
+            points(&[(2, 5), (2, 4), (1, 3), (1, 2), (1, 1)]),
+            "unexpected Bresenham line",
+        );
+
+        assert_eq!(
+            bresenham_line(Point::new(3, 3), Point::new(3, 3)),
+            points(&[(3, 3)]),
+            "unexpected Bresenham line",
+        );
+
+        /* the corners are crossed between two nodes */
+        assert_eq!(
+            supercover_line(Point::new(0, 0), Point::new(2, 2)),
+            points(&[(0, 0), (1, 0), (0, 1), (1, 1), (2, 1), (1, 2), (2, 2)]),
+            "unexpected supercover line",
+        );
+
+        assert_eq!(
+            supercover_line(Point::new(4, 0), Point::new(0, 1)),
+            points(&[(4, 0), (3, 0), (2, 0), (2, 1), (1, 1), (0, 1)]),
+            "unexpected supercover line",
+        );
+
+        assert_eq!(
+            supercover_line(Point::new(0, 0), Point::new(2, 1)),
+            points(&[(0, 0), (1, 0), (1, 1), (2, 1)]),
+            "unexpected supercover line",
+        );
+    }
+
+    #[test]
+    fn test_line_of_sight_and_raycast() {
+
+        const WIDTH: usize = 10;
+
+        let mut grid = Grid::new(WIDTH, WIDTH);
+
+        /* the diagonal from the top left corner goes between two walls */
+        grid.set_blocked(1, true)
+            .unwrap();
+        grid.set_blocked(WIDTH, true)
+            .unwrap();
+
+        let expected_sights = [
+            (Movement::FourDirections, false),
+            (Movement::EightDirections, true),
+            (Movement::EightDirectionsNoCornerCutting, false),
+            (Movement::EightDirectionsNoSqueezing, false),
+        ];
+
+        for &(movement, expected_sight) in expected_sights.iter() {
+
+            grid.set_movement(movement);
+
+            assert_eq!(
+                grid.has_line_of_sight(0, WIDTH * 3 + 3),
+                Ok(expected_sight),
+                "unexpected line of sight through the corner",
+            );
+
+            assert_eq!(
+                grid.raycast(0, 1.0, 1.0, 20.0),
+                Ok(if expected_sight { None } else { Some(1) }),
+                "unexpected raycast through the corner",
+            );
+        }
+
+        /* one wall only: only cutting the corner is forbidden */
+        grid.set_blocked(WIDTH, false)
+            .unwrap();
+
+        let expected_sights = [
+            (Movement::FourDirections, false),
+            (Movement::EightDirections, true),
+            (Movement::EightDirectionsNoCornerCutting, false),
+            (Movement::EightDirectionsNoSqueezing, true),
+        ];
+
+        for &(movement, expected_sight) in expected_sights.iter() {
+
+            grid.set_movement(movement);
+
+            assert_eq!(
+                grid.has_line_of_sight(WIDTH * 3 + 3, 0),
+                Ok(expected_sight),
+                "unexpected line of sight through the corner",
+            );
+        }
+
+        grid.set_movement(Movement::EightDirections);
+        grid.set_blocked(WIDTH * 5 + 6, true)
+            .unwrap();
+
+        assert_eq!(
+            grid.has_line_of_sight(WIDTH * 5, WIDTH * 5 + 9),
+            Ok(false),
+            "the wall must hide the node",
+        );
+
+        assert_eq!(
+            grid.has_line_of_sight(WIDTH * 5, WIDTH * 5 + 6),
+            Ok(false),
+            "an unusuable node cannot be seen",
+        );
+
+        assert_eq!(
+            grid.has_line_of_sight(WIDTH * 5, WIDTH * WIDTH),
+            Err(PathError::OutOfBounds),
+            "unexpected line of sight result",
+        );
+
+        /* the wall is reached 5.5 nodes away from the centre */
+        assert_eq!(
+            grid.raycast(WIDTH * 5, 1.0, 0.0, 5.4),
+            Ok(None),
+            "the ray must end before the wall",
+        );
+
+        assert_eq!(
+            grid.raycast(WIDTH * 5, 2.0, 0.0, 5.6),
+            Ok(Some(WIDTH * 5 + 6)),
+            "the ray must reach the wall",
+        );
+
+        assert_eq!(
+            grid.raycast(WIDTH * 5, -1.0, 0.0, 20.0),
+            Ok(None),
+            "the ray must leave the grid",
+        );
+
+        assert_eq!(
+            grid.raycast(WIDTH * 5 + 6, 1.0, 0.0, 20.0),
+            Ok(Some(WIDTH * 5 + 6)),
+            "the ray must stop on its unusuable first node",
+        );
+    }
+
+    #[test]
+    fn test_line_of_sight_matches_raycast() {
+
+        const WIDTH: usize = 24;
+        const HEIGHT: usize = 16;
+        const QUERIES: usize = 200;
+
+        let movements = [
+            Movement::FourDirections,
+            Movement::EightDirections,
+            Movement::EightDirectionsNoCornerCutting,
+            Movement::EightDirectionsNoSqueezing,
+        ];
+
+        let mut random = get_random_generator(0x51d3);
+
+        for movement in movements.iter() {
+
+            let mut nodes = Nodes::new(WIDTH, HEIGHT, 0, 1);
+            nodes.set_movement(*movement);
+
+            let weights = generate_random_weights(
+                &mut random,
+                WIDTH * HEIGHT,
+                20,
+                0,
+                1,
+            );
+
+            nodes.set_weights(&weights)
+                .unwrap();
+
+            let grid = nodes.get_grid();
+
+            for _ in 0..QUERIES {
+
+                let from = random(WIDTH * HEIGHT);
+                let to = random(WIDTH * HEIGHT);
+
+                if
+                    from == to ||
+                    !grid.get_node(from).is_usuable() ||
+                    !grid.get_node(to).is_usuable()
+                {
+                    continue;
+                }
+
+                let horizontal = (to % WIDTH) as f64 - (from % WIDTH) as f64;
+                let vertical = (to / WIDTH) as f64 - (from / WIDTH) as f64;
+
+                assert_eq!(
+                    nodes.has_line_of_sight(from, to)
+                        .unwrap(),
+                    nodes.raycast(
+                        from,
+                        horizontal,
+                        vertical,
+                        horizontal.hypot(vertical),
+                    ).unwrap().is_none(),
+                    "the ray must reach the visible nodes only",
+                );
+
+                assert_eq!(
+                    grid.has_line_of_sight(from, to),
+                    grid.has_line_of_sight(to, from),
+                    "the line of sight must be symmetric",
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_path_compression_and_string_pulling() {
+
+        const WIDTH: usize = 5;
+
+        let grid = Grid::new(WIDTH, WIDTH);
+
+        assert_eq!(
+            compress_path(&grid, 0, &[1, 2, 3, 8, 13]),
+            vec![3, 13],
+            "the path must turn once",
+        );
+
+        assert_eq!(
+            compress_path(&grid, 0, &[6, 12, 13, 14]),
+            vec![12, 14],
+            "the diagonal must be one segment",
+        );
+
+        assert_eq!(
+            compress_path(&grid, 0, &[]),
+            Vec::<usize>::new(),
+            "the empty path has no waypoint",
+        );
+
+        assert_eq!(
+            pull_string(&grid, 0, &[1, 2, 3, 8, 13]),
+            vec![13],
+            "the arrival must be in line of sight",
+        );
+
+        /* a wall in the middle of the grid, the path goes around it */
+        let mut nodes = Nodes::new(12, 12, 12 * 6 + 1, 12 * 6 + 10);
+
+        for line in 2..10 {
+            nodes.set_blocked(line * 12 + 5, true)
+                .unwrap();
+        }
+
+        let path = nodes.research_path()
+            .unwrap();
+        let grid = nodes.get_grid();
+        let departure = nodes.get_departure_index();
+
+        let compressed_path = compress_path(grid, departure, &path);
+        let pulled_path = pull_string(grid, departure, &path);
+
+        assert!(
+            pulled_path.len() < compressed_path.len(),
+            "the string pulling must remove waypoints",
+        );
+
+        assert_eq!(
+            pull_string(grid, departure, &compressed_path),
+            pulled_path,
+            "the waypoints must be pulled like the path",
+        );
+
+        let mut previous = departure;
+
+        for waypoint in pulled_path.iter() {
+
+            assert!(
+                path.contains(waypoint),
+                "the waypoints must be nodes of the path",
+            );
+
+            assert_eq!(
+                grid.has_line_of_sight(previous, *waypoint),
+                Ok(true),
+                "the waypoints must be in line of sight",
+            );
+
+            previous = *waypoint;
+        }
+
+        assert_eq!(
+            pulled_path.last(),
+            path.last(),
+            "the path must end on the arrival",
+        );
+    }
+
+    #[test]
+    fn test_curve_sampling() {
+
+        const WIDTH: usize = 30;
+        const HEIGHT: usize = 20;
+        const RESEARCHES: usize = 30;
+        const SAMPLES: usize = 8;
+
+        let mut random = get_random_generator(0xc0de);
+
+        let mut grid = Grid::new(WIDTH, HEIGHT);
+        let weights = generate_random_weights(
+            &mut random,
+            WIDTH * HEIGHT,
+            30,
+            0,
+            1,
+        );
+
+        grid.set_weights(&weights)
+            .unwrap();
+
+        let get_centre = |index: usize| (
+            (index % WIDTH) as f64 + 0.5,
+            (index / WIDTH) as f64 + 0.5,
+        );
+
+        let mut search = SearchContext::new();
+        let mut fallbacks = 0;
+
+        for _ in 0..RESEARCHES {
+
+            let departure = random(WIDTH * HEIGHT);
+            let arrival = random(WIDTH * HEIGHT);
+
+            let path = match search.research_path(&grid, departure, arrival) {
+                Ok(path) => path,
+                Err(_) => continue,
+            };
+
+            let waypoints = pull_string(&grid, departure, &path);
+
+            for curve in [Curve::CatmullRom, Curve::Bezier].iter() {
+
+                let points = sample_curve(
+                    &grid,
+                    departure,
+                    &waypoints,
+                    *curve,
+                    SAMPLES,
+                );
+
+                assert_eq!(
+                    (points[0], points[points.len() - 1]),
+                    (get_centre(departure), get_centre(arrival)),
+                    "the curve must go from the departure to the arrival",
+                );
+
+                for &(x, y) in points.iter() {
+
+                    /* the corners follow the movement model */
+                    if x.fract() == 0.0 && y.fract() == 0.0 {
+                        continue;
+                    }
+
+                    assert!(
+                        grid.get_node(y as usize * WIDTH + x as usize)
+                            .is_usuable(),
+                        "the curve must stay on usuable nodes",
+                    );
+                }
+
+                if *curve == Curve::CatmullRom {
+
+                    assert!(
+                        waypoints.iter().all(|waypoint| {
+
+                            let (x, y) = get_centre(*waypoint);
+
+                            points.iter().any(|point| {
+                                (point.0 - x).abs() < 1e-9 &&
+                                    (point.1 - y).abs() < 1e-9
+                            })
+                        }),
+                        "the spline must go through the waypoints",
+                    );
+
+                    /* the straight segments replace the unsafe parts */
+                    if points.len() < waypoints.len() * SAMPLES + 1 {
+                        fallbacks += 1;
+                    }
+                }
+            }
+        }
+
+        assert!(
+            fallbacks > 0,
+            "some parts of the curves must be replaced",
+        );
+    }
+
+    #[test]
+    fn test_curve_sampling_corners() {
+
+        /* the rounded corner touches the corner of the wall
+           . . . . . .
+           . . # . . .
+           . . . . . .
+           . . . . . .
+           . D . . . . */
+        let mut grid = Grid::new(6, 5);
+        let waypoints = [1, 5];
+
+        assert_eq!(
+            sample_curve(&grid, 25, &waypoints, Curve::Bezier, 8).len(),
+            11,
+            "the curve must be kept without any wall",
+        );
+
+        grid.set_blocked(8, true)
+            .unwrap();
+
+        for movement in [
+            Movement::EightDirections,
+            Movement::EightDirectionsNoCornerCutting,
+        ].iter() {
+
+            grid.set_movement(*movement);
+
+            let points = sample_curve(&grid, 25, &waypoints, Curve::Bezier, 8);
+
+            /* the polyline goes straight from one point to the next one */
+            for chord in points.windows(2) {
+
+                let get_index = |(x, y): (f64, f64)| {
+                    y as usize * grid.get_width() + x as usize
+                };
+
+                assert_eq!(
+                    grid.has_line_of_sight(get_index(chord[0]), get_index(chord[1])),
+                    Ok(true),
+                    "the chords must not cut through the wall",
+                );
+            }
+
+            assert_eq!(
+                points,
+                vec![
+                    (1.5, 4.5),
+                    (1.5, 2.5),
+                    (1.5, 0.5),
+                    (3.5, 0.5),
+                    (5.5, 0.5),
+                ],
+                "the straight segments must replace the curve around the wall",
+            );
+        }
     }
 }